@@ -0,0 +1,122 @@
+// Closed-form Jalali <-> JDN conversion vs. the year-by-year loops it replaced.
+//
+//   cargo bench --bench conversion
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use scal_lib::*;
+
+// The previous implementation, kept verbatim as the baseline to beat.
+mod legacy {
+    use scal_lib::*;
+
+    fn jalali_to_jdn(jy: i32, jm: u8, jd: u8) -> i64 {
+        let mut input_yday_0_indexed: i32 = 0;
+        for m_iter in 1..jm {
+            input_yday_0_indexed += days_in_month(jy, m_iter) as i32;
+        }
+        input_yday_0_indexed += jd as i32 - 1;
+
+        let mut p_days_from_unix_epoch: i64 = 0;
+
+        if jy >= JALALI_YEAR_AT_UNIX_EPOCH {
+            for i_y in JALALI_YEAR_AT_UNIX_EPOCH..jy {
+                p_days_from_unix_epoch += if is_leap(i_y) {
+                    DAYS_IN_LEAP_YEAR
+                } else {
+                    DAYS_IN_COMMON_YEAR
+                };
+            }
+            p_days_from_unix_epoch +=
+                input_yday_0_indexed as i64 - JALALI_YDAY_AT_UNIX_EPOCH as i64;
+        } else {
+            for i_y in jy..JALALI_YEAR_AT_UNIX_EPOCH {
+                p_days_from_unix_epoch -= if is_leap(i_y) {
+                    DAYS_IN_LEAP_YEAR
+                } else {
+                    DAYS_IN_COMMON_YEAR
+                };
+            }
+            p_days_from_unix_epoch -=
+                JALALI_YDAY_AT_UNIX_EPOCH as i64 - input_yday_0_indexed as i64;
+        }
+
+        p_days_from_unix_epoch + UNIX_EPOCH_JDN
+    }
+
+    fn yday_to_month_day(year: i32, yday: i32) -> (u8, u8) {
+        let mut p_day_in_year = yday + 1;
+        for month in 1..LAST_MONTH_INDEX {
+            let days_in_current_month = days_in_month(year, month) as i32;
+            if p_day_in_year > days_in_current_month {
+                p_day_in_year -= days_in_current_month;
+            } else {
+                return (month, p_day_in_year as u8);
+            }
+        }
+        (LAST_MONTH_INDEX, p_day_in_year as u8)
+    }
+
+    fn jdn_to_jalali(jdn: i64) -> (i32, u8, u8) {
+        let mut p_offset = jdn - UNIX_EPOCH_JDN + JALALI_YDAY_AT_UNIX_EPOCH as i64;
+        let mut current_jalali_year = JALALI_YEAR_AT_UNIX_EPOCH;
+
+        if p_offset >= 0 {
+            loop {
+                let days_in_current_jalali_year = if is_leap(current_jalali_year) {
+                    DAYS_IN_LEAP_YEAR
+                } else {
+                    DAYS_IN_COMMON_YEAR
+                };
+                if p_offset < days_in_current_jalali_year {
+                    break;
+                }
+                p_offset -= days_in_current_jalali_year;
+                current_jalali_year += 1;
+            }
+        } else {
+            loop {
+                current_jalali_year -= 1;
+                p_offset += if is_leap(current_jalali_year) {
+                    DAYS_IN_LEAP_YEAR
+                } else {
+                    DAYS_IN_COMMON_YEAR
+                };
+                if p_offset >= 0 {
+                    break;
+                }
+            }
+        }
+
+        let (month, day) = yday_to_month_day(current_jalali_year, p_offset as i32);
+        (current_jalali_year, month, day)
+    }
+
+    // Jalali -> JDN -> Jalali, the same work as `round_trip` below.
+    pub fn round_trip(jy: i32, jm: u8, jd: u8) -> (i32, u8, u8) {
+        jdn_to_jalali(jalali_to_jdn(jy, jm, jd))
+    }
+}
+
+fn round_trip(jy: i32, jm: u8, jd: u8) -> (i32, u8, u8) {
+    jdn_to_jalali(jalali_to_jdn(jy, jm, jd))
+}
+
+fn bench_round_trip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jalali_round_trip");
+    for year in [1, 1403, 9999] {
+        assert_eq!(round_trip(year, 12, 29), legacy::round_trip(year, 12, 29));
+
+        group.bench_with_input(BenchmarkId::new("closed_form", year), &year, |b, &y| {
+            b.iter(|| round_trip(black_box(y), black_box(12), black_box(29)))
+        });
+        group.bench_with_input(BenchmarkId::new("year_loop", year), &year, |b, &y| {
+            b.iter(|| legacy::round_trip(black_box(y), black_box(12), black_box(29)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_round_trip);
+criterion_main!(benches);
@@ -0,0 +1,259 @@
+//! Solar astronomy for the astronomical leap rule and Tahvil-e Sal (the
+//! moment of the March equinox that starts each Jalali year).
+//!
+//! March equinox after Jean Meeus, *Astronomical Algorithms* (2nd ed.),
+//! chapter 27, and Delta T after the Espenak-Meeus polynomials.
+
+use std::f64::consts::PI;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::leap::JALALI_TO_GREGORIAN_YEAR_OFFSET;
+use crate::{JalaliError, MAX_JALALI_YEAR, MIN_JALALI_YEAR};
+
+pub(crate) const J2000_JDE: f64 = 2_451_545.0;
+pub(crate) const DAYS_PER_JULIAN_CENTURY: f64 = 36_525.0;
+pub(crate) const SECONDS_PER_DAY: f64 = 86_400.0;
+pub(crate) const MEAN_TROPICAL_YEAR: f64 = 365.242_189;
+// Julian Date of 1970-01-01T00:00:00Z.
+pub(crate) const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+// Meeus' mean-equinox polynomials are only fitted for -1000..=3000;
+// outside that the mean tropical year is used to extrapolate.
+const MEEUS_FIRST_YEAR: i32 = -1000;
+const MEEUS_LAST_YEAR: i32 = 3000;
+
+// Table 27.C: periodic terms A, B (degrees), C (degrees per century).
+const EQUINOX_PERIODIC_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96, 1934.136),
+    (203.0, 337.23, 32964.467),
+    (199.0, 342.08, 20.186),
+    (182.0, 27.85, 445267.112),
+    (156.0, 73.14, 45036.886),
+    (136.0, 171.52, 22518.443),
+    (77.0, 222.54, 65928.934),
+    (74.0, 296.72, 3034.906),
+    (70.0, 243.58, 9037.513),
+    (58.0, 119.81, 33718.147),
+    (52.0, 297.17, 150.678),
+    (50.0, 21.02, 2281.226),
+    (45.0, 247.54, 29929.562),
+    (44.0, 325.15, 31555.956),
+    (29.0, 60.93, 4443.417),
+    (18.0, 155.12, 67555.328),
+    (17.0, 288.79, 4562.452),
+    (16.0, 198.04, 62894.029),
+    (14.0, 199.76, 31436.921),
+    (12.0, 95.39, 14577.848),
+    (12.0, 287.11, 31931.756),
+    (12.0, 320.81, 34777.259),
+    (9.0, 227.73, 1222.114),
+    (8.0, 15.45, 16859.074),
+];
+
+fn cos_deg(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).cos()
+}
+
+fn sin_deg(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).sin()
+}
+
+// Table 27.A / 27.B: mean March equinox in Julian Ephemeris Days.
+fn mean_march_equinox_jde(gregorian_year: i32) -> f64 {
+    if gregorian_year < MEEUS_FIRST_YEAR {
+        return mean_march_equinox_jde(MEEUS_FIRST_YEAR)
+            - (MEEUS_FIRST_YEAR - gregorian_year) as f64 * MEAN_TROPICAL_YEAR;
+    }
+    if gregorian_year > MEEUS_LAST_YEAR {
+        return mean_march_equinox_jde(MEEUS_LAST_YEAR)
+            + (gregorian_year - MEEUS_LAST_YEAR) as f64 * MEAN_TROPICAL_YEAR;
+    }
+    if gregorian_year < 1000 {
+        let y = gregorian_year as f64 / 1000.0;
+        1_721_139.291_89 + 365_242.137_40 * y + 0.061_34 * y.powi(2) + 0.001_11 * y.powi(3)
+            - 0.000_71 * y.powi(4)
+    } else {
+        let y = (gregorian_year as f64 - 2000.0) / 1000.0;
+        2_451_623.809_84 + 365_242.374_04 * y + 0.051_69 * y.powi(2)
+            - 0.004_11 * y.powi(3)
+            - 0.000_57 * y.powi(4)
+    }
+}
+
+/// Instant of the March equinox of `gregorian_year` in Julian Ephemeris
+/// Days (Terrestrial Time).
+pub(crate) fn march_equinox_jde(gregorian_year: i32) -> f64 {
+    let jde0 = mean_march_equinox_jde(gregorian_year);
+    let t = (jde0 - J2000_JDE) / DAYS_PER_JULIAN_CENTURY;
+    let w = 35_999.373 * t - 2.47;
+    let delta_lambda = 1.0 + 0.0334 * cos_deg(w) + 0.0007 * cos_deg(2.0 * w);
+    let s: f64 = EQUINOX_PERIODIC_TERMS
+        .iter()
+        .map(|&(a, b, c)| a * cos_deg(b + c * t))
+        .sum();
+    jde0 + 0.000_01 * s / delta_lambda
+}
+
+/// TT - UT in seconds for a decimal year (Espenak-Meeus).
+pub(crate) fn delta_t_seconds(year: f64) -> f64 {
+    let y = year;
+    if y < -500.0 {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    } else if y < 500.0 {
+        let u = y / 100.0;
+        10583.6 - 1014.41 * u + 33.78311 * u.powi(2) - 5.952053 * u.powi(3) - 0.1798452 * u.powi(4)
+            + 0.022174192 * u.powi(5)
+            + 0.0090316521 * u.powi(6)
+    } else if y < 1600.0 {
+        let u = (y - 1000.0) / 100.0;
+        1574.2 - 556.01 * u + 71.23472 * u.powi(2) + 0.319781 * u.powi(3)
+            - 0.8503463 * u.powi(4)
+            - 0.005050998 * u.powi(5)
+            + 0.0083572073 * u.powi(6)
+    } else if y < 1700.0 {
+        let t = y - 1600.0;
+        120.0 - 0.9808 * t - 0.01532 * t.powi(2) + t.powi(3) / 7129.0
+    } else if y < 1800.0 {
+        let t = y - 1700.0;
+        8.83 + 0.1603 * t - 0.0059285 * t.powi(2) + 0.00013336 * t.powi(3) - t.powi(4) / 1_174_000.0
+    } else if y < 1860.0 {
+        let t = y - 1800.0;
+        13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3)
+            - 0.00037436 * t.powi(4)
+            + 0.0000121272 * t.powi(5)
+            - 0.0000001699 * t.powi(6)
+            + 0.000000000875 * t.powi(7)
+    } else if y < 1900.0 {
+        let t = y - 1860.0;
+        7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4)
+            + t.powi(5) / 233_174.0
+    } else if y < 1920.0 {
+        let t = y - 1900.0;
+        -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+    } else if y < 1941.0 {
+        let t = y - 1920.0;
+        21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+    } else if y < 1961.0 {
+        let t = y - 1950.0;
+        29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+    } else if y < 1986.0 {
+        let t = y - 1975.0;
+        45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+    } else if y < 2005.0 {
+        let t = y - 2000.0;
+        63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+            + 0.0017275 * t.powi(3)
+            + 0.000651814 * t.powi(4)
+            + 0.00002373599 * t.powi(5)
+    } else if y < 2050.0 {
+        let t = y - 2000.0;
+        62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+    } else if y < 2150.0 {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u - 0.5628 * (2150.0 - y)
+    } else {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    }
+}
+
+/// March equinox of `gregorian_year` as a Julian Date in Universal Time.
+pub(crate) fn march_equinox_ut(gregorian_year: i32) -> f64 {
+    let jde = march_equinox_jde(gregorian_year);
+    // The equinox falls around March 20, i.e. 0.22 into the year.
+    jde - delta_t_seconds(gregorian_year as f64 + 0.22) / SECONDS_PER_DAY
+}
+
+/// Apparent minus mean solar time, in days, at Julian Date `jd`.
+pub(crate) fn equation_of_time(jd: f64) -> f64 {
+    let n = jd - J2000_JDE;
+    let mean_longitude = 280.460 + 0.985_647_4 * n;
+    let mean_anomaly = 357.528 + 0.985_600_3 * n;
+    let ecliptic_longitude =
+        mean_longitude + 1.915 * sin_deg(mean_anomaly) + 0.020 * sin_deg(2.0 * mean_anomaly);
+    let obliquity = 23.439 - 0.000_000_4 * n;
+    let right_ascension = (cos_deg(obliquity) * sin_deg(ecliptic_longitude))
+        .atan2(cos_deg(ecliptic_longitude))
+        * 180.0
+        / PI;
+    let difference = (mean_longitude - right_ascension).rem_euclid(360.0);
+    let difference = if difference > 180.0 {
+        difference - 360.0
+    } else {
+        difference
+    };
+    // One degree of hour angle is four minutes.
+    difference / 360.0
+}
+
+/// Instant of Tahvil-e Sal (the March equinox that begins Jalali year `jy`)
+/// in UTC, rounded to the second.
+///
+/// The model is good to a minute or two for Gregorian years -1000..3000 and
+/// degrades gradually outside that range.
+pub fn vernal_equinox_utc(jy: i32) -> Result<DateTime<Utc>, JalaliError> {
+    if !(MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(&jy) {
+        return Err(JalaliError::OutOfRange);
+    }
+    let jd = march_equinox_ut(jy + JALALI_TO_GREGORIAN_YEAR_OFFSET);
+    let seconds = ((jd - UNIX_EPOCH_JD) * SECONDS_PER_DAY).round() as i64;
+    DateTime::from_timestamp(seconds, 0).ok_or(JalaliError::OutOfRange)
+}
+
+/// Tahvil-e Sal of Jalali year `jy` in the time zone `tz`, e.g.
+/// `vernal_equinox(1404, &chrono_tz::Asia::Tehran)`.
+pub fn vernal_equinox<Tz: TimeZone>(jy: i32, tz: &Tz) -> Result<DateTime<Tz>, JalaliError> {
+    vernal_equinox_utc(jy).map(|instant| instant.with_timezone(tz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    fn unix_minutes(jd: f64) -> i64 {
+        ((jd - UNIX_EPOCH_JD) * 1440.0).round() as i64
+    }
+
+    #[test]
+    fn test_march_equinox_matches_published_times() {
+        // 2024-03-20 03:06 UTC and 2025-03-20 09:01 UTC.
+        let cases = [(2024, 28_515_066_i64), (2025, 29_041_021_i64)];
+        for (year, expected_minutes) in cases {
+            let got = unix_minutes(march_equinox_ut(year));
+            assert!(
+                (got - expected_minutes).abs() <= 2,
+                "equinox {} off by {} minutes",
+                year,
+                got - expected_minutes
+            );
+        }
+    }
+
+    #[test]
+    fn test_equation_of_time_near_equinox() {
+        // Around March 20 the sun runs about 7.5 minutes slow.
+        let minutes = equation_of_time(march_equinox_ut(2024)) * 1440.0;
+        assert!((-8.5..-6.5).contains(&minutes), "got {}", minutes);
+    }
+
+    #[test]
+    fn test_vernal_equinox_in_tehran() {
+        // Tahvil-e Sal 1404 was announced for 12:31:30 Tehran time on 2025-03-20.
+        let tahvil = vernal_equinox(1404, &chrono_tz::Asia::Tehran).unwrap();
+        assert_eq!((tahvil.year(), tahvil.month(), tahvil.day()), (2025, 3, 20));
+        let minutes = tahvil.hour() * 60 + tahvil.minute();
+        assert!(minutes.abs_diff(12 * 60 + 31) <= 2, "got {}", tahvil);
+
+        // 2024-03-20 03:06 UTC.
+        let utc = vernal_equinox_utc(1403).unwrap();
+        assert!(
+            (utc.timestamp() / 60 - 28_515_066).abs() <= 2,
+            "got {}",
+            utc
+        );
+        assert_eq!(vernal_equinox_utc(0), Err(JalaliError::OutOfRange));
+    }
+}
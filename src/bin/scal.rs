@@ -1,6 +1,6 @@
 // Persian‑style `cal` replacement.
 
-use chrono::{Datelike, Local};
+use chrono::Local;
 use clap::Parser;
 use colored::*;
 use scal_lib::*;
@@ -20,11 +20,24 @@ pub const MAX_DAYS_LINE_WIDTH: usize = JULIAN_DAY_CELL_WIDTH * (scal_lib::WEEK_D
     name = "scal"
 )]
 struct Cli {
+    #[arg(value_parser = parse_number::<i32>)]
     year: Option<i32>,
     #[arg(short = 'P', long, help = "Display year based on Pahlavi year")]
     pahlavi: bool,
+    #[arg(
+        long,
+        value_name = "ERA",
+        help = "Era of years: solar-hijri, imperial or yazdgerdi"
+    )]
+    era: Option<Era>,
     #[arg(short = 'p', long, help = "Display Farsi numbers and names")]
     persian_output: bool,
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help = "Locale of names: latin, fa, prs, prs-latn, ps or ckb"
+    )]
+    locale: Option<Locale>,
     #[arg(
         short = 'e',
         long,
@@ -38,8 +51,10 @@ struct Cli {
 }
 
 struct PrintConfig {
-    pahlavi_active: bool,
-    persian_output_active: bool,
+    era: Era,
+    // Shown after the year, e.g. "Pahlavi" for `-P`.
+    era_label: Option<&'static str>,
+    locale: Locale,
     english_days_active: bool,
     julian_days_active: bool,
 }
@@ -54,6 +69,20 @@ fn main() {
         std::process::exit(1);
     }
 
+    if cli.persian_output && cli.locale.is_some() {
+        eprintln!(
+            "Error: The -p (Persian output) and --locale options cannot be used together. Example: scal -p 1379 or scal --locale prs 1379"
+        );
+        std::process::exit(1);
+    }
+
+    if cli.pahlavi && cli.era.is_some() {
+        eprintln!(
+            "Error: The -P (Pahlavi) and --era options cannot be used together. Example: scal -P 1357 or scal --era imperial 1357"
+        );
+        std::process::exit(1);
+    }
+
     if cli.current_year_view && cli.year.is_some() {
         eprintln!(
             "Error: The -y option cannot be used when a specific year is provided. Example: scal -y"
@@ -61,306 +90,213 @@ fn main() {
         std::process::exit(1);
     }
 
-    let (cur_jy, cur_jm, cur_jd) = {
-        let now = Local::now().naive_local().date();
-        gregorian_to_jalali(now.year(), now.month(), now.day())
+    let era = if cli.pahlavi {
+        Era::Imperial
+    } else {
+        cli.era.unwrap_or_default()
     };
+    // `--era` reads the year in that era, like sdate; `-P` keeps taking a
+    // Solar Hijri year.
+    let input_year = cli.year.map(|y| {
+        if cli.pahlavi {
+            y
+        } else {
+            era.year_to_solar_hijri(y)
+        }
+    });
 
+    if let (Some(y), Some(jy)) = (cli.year, input_year)
+        && let Err(e) = JalaliDate::try_from_ymd(jy, 1, 1)
+    {
+        eprintln!(
+            "Error: Cannot display year {}: {}. Example: scal 1403",
+            y, e
+        );
+        std::process::exit(1);
+    }
+
+    let today = JalaliDate::try_from(Local::now().date_naive())
+        .expect("today is within the supported Jalali range");
+
+    let locale = if cli.persian_output {
+        Locale::Persian
+    } else {
+        cli.locale.unwrap_or_else(Locale::from_env)
+    };
+    // `-P` keeps its original "Pahlavi" label; `--era` uses the era's name.
+    let era_label = if cli.pahlavi {
+        Some(if locale.is_arabic_script() {
+            "پهلوی"
+        } else {
+            "Pahlavi"
+        })
+    } else if era != Era::SolarHijri {
+        Some(era.name(locale))
+    } else {
+        None
+    };
     let print_config = PrintConfig {
-        pahlavi_active: cli.pahlavi,
-        persian_output_active: cli.persian_output,
+        era,
+        era_label,
+        locale,
         english_days_active: cli.english_days,
         julian_days_active: cli.julian_days,
     };
 
-    match cli.year {
-        Some(y) => {
-            let display_jy = if print_config.pahlavi_active {
-                y + 1180
-            } else {
-                y
-            };
-            let calc_jy = y; // The original year
-            print_year(display_jy, calc_jy, cur_jy, cur_jm, cur_jd, &print_config);
-        }
-        None => {
-            if cli.current_year_view {
-                let display_jy = if print_config.pahlavi_active {
-                    cur_jy + 1180
-                } else {
-                    cur_jy
-                };
-                // For current year
-                print_year(display_jy, cur_jy, cur_jy, cur_jm, cur_jd, &print_config);
-            } else {
-                let display_jy = if print_config.pahlavi_active {
-                    cur_jy + 1180
-                } else {
-                    cur_jy
-                };
-                let calc_jy = cur_jy;
-                print_month(
-                    display_jy,
-                    calc_jy,
-                    cur_jm,
-                    cur_jy,
-                    cur_jm,
-                    cur_jd,
-                    &print_config,
-                );
-            }
-        }
+    let year = input_year.unwrap_or(today.year());
+    let display_jy = print_config.era.year_from_solar_hijri(year);
+    if cli.year.is_some() || cli.current_year_view {
+        let grid = YearGrid::new(year).unwrap_or_else(|e| {
+            eprintln!("Error: Cannot display year {}: {}.", year, e);
+            std::process::exit(1);
+        });
+        print_year(display_jy, &grid.with_today(today), &print_config);
+    } else {
+        let grid = MonthGrid::new(year, today.month()).unwrap_or_else(|e| {
+            eprintln!(
+                "Error: Cannot display month {} of year {}: {}.",
+                today.month(),
+                year,
+                e
+            );
+            std::process::exit(1);
+        });
+        print_month(display_jy, &grid.with_today(today), &print_config);
     }
 }
 
-fn print_month(
-    display_jy: i32,
-    calc_jy: i32,
-    jm: u8,
-    cur_jy: i32,
-    cur_jm: u8,
-    cur_jd: u8,
-    config: &PrintConfig,
-) {
-    let day_cell_width = if config.julian_days_active {
+fn day_cell_width(config: &PrintConfig) -> usize {
+    if config.julian_days_active {
         JULIAN_DAY_CELL_WIDTH
     } else {
         BASE_DAY_CELL_WIDTH
-    };
-    let current_calendar_width = day_cell_width * (scal_lib::WEEK_DAYS_TOTAL as usize);
-
-    let month_name_str = if config.persian_output_active {
-        PERSIAN_MONTH_NAMES[(jm - 1) as usize]
-    } else {
-        MONTH_NAMES[(jm - 1) as usize]
-    };
-    let display_year_str = if config.persian_output_active {
-        to_persian_numerals(&display_jy.to_string())
-    } else {
-        display_jy.to_string()
-    };
-
-    let mut title_str = format!("{} {}", month_name_str, display_year_str);
-    if config.pahlavi_active {
-        if config.persian_output_active {
-            title_str.push_str(" (پهلوی)");
-        } else {
-            title_str.push_str(" (Pahlavi)");
-        }
     }
-    println!("{:^width$}", title_str, width = current_calendar_width);
+}
 
-    let week_days_to_use = if config.persian_output_active {
-        PERSIAN_WEEK_DAYS_AB.as_slice()
-    } else if config.english_days_active {
+fn month_name(grid: &MonthGrid, config: &PrintConfig) -> String {
+    let first_day = grid.days().next().expect("a month has days").date();
+    first_day.format("%B").locale(config.locale).to_string()
+}
+
+// Weekday names, Saturday first, with Friday in red.
+fn week_days_line(config: &PrintConfig) -> String {
+    let day_cell_width = day_cell_width(config);
+    let week_days_to_use = if config.english_days_active {
         ENGLISH_WEEK_DAYS_AB.as_slice()
     } else {
-        WEEK_DAYS_AB.as_slice()
+        config.locale.weekday_names_short().as_slice()
     };
+    let mut line = String::with_capacity(MAX_DAYS_LINE_WIDTH);
     for (i, &day_name) in week_days_to_use.iter().enumerate() {
         if i == JALALI_FRIDAY_INDEX {
-            print!("{:>width$}", day_name.red(), width = day_cell_width);
+            line.push_str(&format!(
+                "{:>width$}",
+                day_name.red(),
+                width = day_cell_width
+            ));
         } else {
-            print!("{:>width$}", day_name, width = day_cell_width);
+            line.push_str(&format!("{:>width$}", day_name, width = day_cell_width));
         }
     }
-    println!();
+    line
+}
 
-    let first_col = match first_weekday(calc_jy, jm) {
-        Some(col) => col,
-        None => std::process::exit(1),
+// One week of day numbers; today is reversed and weekend days are red.
+fn week_line(week: &GridWeek, config: &PrintConfig) -> String {
+    let day_cell_width = day_cell_width(config);
+    let day_cell_pattern = if config.julian_days_active {
+        "%-j"
+    } else {
+        "%-d"
     };
+    let days_in_week = week.iter().rposition(Option::is_some).map_or(0, |i| i + 1);
 
-    let dim = days_in_month(calc_jy, jm);
-
-    for _ in 0..first_col {
-        print!("{}", " ".repeat(day_cell_width));
-    }
-
-    let mut col = first_col;
-    for day in 1..=dim {
-        let day_to_display_num = if config.julian_days_active {
-            jalali_day_of_year(calc_jy, jm, day)
-        } else {
-            day as i32
-        };
-        let day_num_str_original = day_to_display_num.to_string();
-        let day_num_str = if config.persian_output_active {
-            to_persian_numerals(&day_num_str_original)
-        } else {
-            day_num_str_original
+    let mut line = String::with_capacity(MAX_DAYS_LINE_WIDTH);
+    for cell in &week[..days_in_week] {
+        let Some(cell) = cell else {
+            line.push_str(&" ".repeat(day_cell_width));
+            continue;
         };
+        let day_num_str = cell
+            .date()
+            .format(day_cell_pattern)
+            .persian_digits(config.locale.native_digits())
+            .to_string();
 
         let padding_len = day_cell_width.saturating_sub(day_num_str.chars().count());
-        let padding = " ".repeat(padding_len);
-
-        let is_today = calc_jy == cur_jy && jm == cur_jm && day == cur_jd;
-        let is_friday = col as usize == JALALI_FRIDAY_INDEX;
-
-        if is_today {
-            print!("{}{}", padding, day_num_str.reversed());
-        } else if is_friday {
-            print!("{}{}", padding, day_num_str.red());
+        line.push_str(&" ".repeat(padding_len));
+        if cell.is_today() {
+            line.push_str(&day_num_str.reversed().to_string());
+        } else if cell.is_weekend() {
+            line.push_str(&day_num_str.red().to_string());
         } else {
-            print!("{}{}", padding, day_num_str);
-        }
-
-        col += 1;
-        if col == scal_lib::WEEK_DAYS_TOTAL {
-            col = 0;
-            println!();
+            line.push_str(&day_num_str);
         }
     }
-    if col != 0 {
-        println!();
-    }
+    line
 }
 
-fn print_year(
-    display_jy: i32,
-    calc_jy: i32,
-    cur_jy: i32,
-    cur_jm: u8,
-    cur_jd: u8,
-    config: &PrintConfig,
-) {
-    let day_cell_width = if config.julian_days_active {
-        JULIAN_DAY_CELL_WIDTH
-    } else {
-        BASE_DAY_CELL_WIDTH
-    };
-    let current_month_sub_calendar_width = day_cell_width * (scal_lib::WEEK_DAYS_TOTAL as usize);
-    let current_year_header_width = current_month_sub_calendar_width * MONTHS_PER_ROW
-        + COLUMN_SPACING.len() * (MONTHS_PER_ROW - 1);
-
-    let mut month_lines: Vec<Vec<String>> = vec![vec![]; 12];
-
-    let active_month_names = if config.persian_output_active {
-        PERSIAN_MONTH_NAMES.as_slice()
-    } else {
-        MONTH_NAMES.as_slice()
-    };
-    let active_week_days = if config.persian_output_active {
-        PERSIAN_WEEK_DAYS_AB.as_slice()
-    } else if config.english_days_active {
-        ENGLISH_WEEK_DAYS_AB.as_slice()
+fn display_year(display_jy: i32, config: &PrintConfig) -> String {
+    if config.locale.native_digits() {
+        to_persian_numerals(&display_jy.to_string())
     } else {
-        WEEK_DAYS_AB.as_slice()
-    };
-
-    for m_idx in 0..12 {
-        let jm = (m_idx + 1) as u8;
-        let month_name_str = active_month_names[m_idx];
-
-        month_lines[m_idx].push(format!(
-            "{:^width$}",
-            month_name_str,
-            width = current_month_sub_calendar_width
-        ));
-
-        let mut day_names_line = String::with_capacity(MAX_DAYS_LINE_WIDTH);
-        for (i, &day_name) in active_week_days.iter().enumerate() {
-            if i == JALALI_FRIDAY_INDEX {
-                day_names_line.push_str(&format!(
-                    "{:>width$}",
-                    day_name.red(),
-                    width = day_cell_width
-                ));
-            } else {
-                day_names_line.push_str(&format!("{:>width$}", day_name, width = day_cell_width));
-            }
-        }
-        month_lines[m_idx].push(day_names_line);
-
-        let first_col = match first_weekday(calc_jy, jm) {
-            Some(col) => col,
-            None => std::process::exit(1),
-        };
-        let dim = days_in_month(calc_jy, jm);
-        let mut current_line = String::with_capacity(MAX_DAYS_LINE_WIDTH);
-
-        for _ in 0..first_col {
-            current_line.push_str(&" ".repeat(day_cell_width));
-        }
-
-        let mut col = first_col;
-        for day in 1..=dim {
-            let day_to_display_num = if config.julian_days_active {
-                jalali_day_of_year(calc_jy, jm, day)
-            } else {
-                day as i32
-            };
-            let day_num_str_original = day_to_display_num.to_string();
-
-            let day_num_str_display = if config.persian_output_active {
-                to_persian_numerals(&day_num_str_original)
-            } else {
-                day_num_str_original
-            };
-
-            let padding_len = day_cell_width.saturating_sub(day_num_str_display.chars().count());
-            let padding = " ".repeat(padding_len);
-
-            let is_today = calc_jy == cur_jy && jm == cur_jm && day == cur_jd;
-            let is_friday = col as usize == JALALI_FRIDAY_INDEX;
+        display_jy.to_string()
+    }
+}
 
-            let formatted_day_part = if is_today {
-                day_num_str_display.as_str().reversed().to_string()
-            } else if is_friday {
-                day_num_str_display.as_str().red().to_string()
-            } else {
-                day_num_str_display
-            };
-            current_line.push_str(&padding);
-            current_line.push_str(&formatted_day_part);
+fn print_month(display_jy: i32, grid: &MonthGrid, config: &PrintConfig) {
+    let current_calendar_width = day_cell_width(config) * (scal_lib::WEEK_DAYS_TOTAL as usize);
 
-            col += 1;
-            if col == scal_lib::WEEK_DAYS_TOTAL {
-                month_lines[m_idx].push(current_line);
-                current_line = String::with_capacity(MAX_DAYS_LINE_WIDTH);
-                col = 0;
-            }
-        }
-        if !current_line.is_empty() {
-            month_lines[m_idx].push(current_line);
-        }
+    let mut title_str = format!(
+        "{} {}",
+        month_name(grid, config),
+        display_year(display_jy, config)
+    );
+    if let Some(label) = config.era_label {
+        title_str.push_str(&format!(" ({})", label));
     }
+    println!("{:^width$}", title_str, width = current_calendar_width);
+    println!("{}", week_days_line(config));
+    for week in grid.weeks() {
+        println!("{}", week_line(week, config));
+    }
+}
 
-    let year_header = if config.persian_output_active {
-        format!(
-            "{} {}",
-            to_persian_numerals(&display_jy.to_string()),
-            if config.pahlavi_active {
-                "(پهلوی)"
-            } else {
-                ""
-            }
-        )
-    } else {
-        format!(
-            "{} {}",
-            display_jy,
-            if config.pahlavi_active {
-                "(Pahlavi)"
-            } else {
-                ""
-            }
-        )
-    };
+fn print_year(display_jy: i32, grid: &YearGrid, config: &PrintConfig) {
+    let current_month_sub_calendar_width =
+        day_cell_width(config) * (scal_lib::WEEK_DAYS_TOTAL as usize);
+    let current_year_header_width = current_month_sub_calendar_width * MONTHS_PER_ROW
+        + COLUMN_SPACING.len() * (MONTHS_PER_ROW - 1);
+
+    let era_label = config
+        .era_label
+        .map_or_else(String::new, |label| format!("({})", label));
+    let year_header = format!("{} {}", display_year(display_jy, config), era_label);
     println!("{:^width$}", year_header, width = current_year_header_width);
     println!();
 
-    for row in 0..4 {
-        let indices = [row * 3, row * 3 + 1, row * 3 + 2];
-        let max_lines = indices
+    let day_names_line = week_days_line(config);
+    for row in grid.rows(MONTHS_PER_ROW) {
+        let month_lines: Vec<Vec<String>> = row
             .iter()
-            .map(|&i| month_lines[i].len())
-            .max()
-            .unwrap_or(0);
+            .map(|month| {
+                let mut lines = vec![
+                    format!(
+                        "{:^width$}",
+                        month_name(month, config),
+                        width = current_month_sub_calendar_width
+                    ),
+                    day_names_line.clone(),
+                ];
+                lines.extend(month.weeks().iter().map(|week| week_line(week, config)));
+                lines
+            })
+            .collect();
+        let max_lines = month_lines.iter().map(Vec::len).max().unwrap_or(0);
 
         for line_idx in 0..max_lines {
-            for (idx, &m_idx) in indices.iter().enumerate() {
-                let line = month_lines[m_idx].get(line_idx).map_or("", |s| s.as_str());
+            for (idx, lines) in month_lines.iter().enumerate() {
+                let line = lines.get(line_idx).map_or("", |s| s.as_str());
                 print!("{:<width$}", line, width = current_month_sub_calendar_width);
                 if idx < MONTHS_PER_ROW - 1 {
                     print!("{COLUMN_SPACING}");
@@ -1,4 +1,4 @@
-use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone, Timelike, Utc};
 use chrono_tz::Tz;
 use clap::Parser;
 use scal_lib::*;
@@ -19,6 +19,8 @@ struct TimeAdjustment {
 
 impl TimeAdjustment {
     fn parse(input: &str) -> Result<Self, &'static str> {
+        let input = normalize_persian(input);
+        let input = input.as_str();
         if input.is_empty() {
             return Err("Error: Empty adjustment string. Example: +5d");
         }
@@ -71,160 +73,145 @@ impl TimeAdjustment {
         })
     }
 
-    fn apply(&self, dt: NaiveDateTime) -> NaiveDateTime {
+    // `era` is the era a set year (`-v 1403y`) is counted in.
+    fn apply<Tz: TimeZone>(
+        &self,
+        dt: &JalaliDateTime<Tz>,
+        era: Era,
+    ) -> Result<JalaliDateTime<Tz>, String> {
         match self.adjustment_type {
             AdjustmentType::Add(_) => self.apply_add(dt),
-            AdjustmentType::Set => self.apply_set(dt),
+            AdjustmentType::Set => self.apply_set(dt, era),
         }
     }
 
-    fn apply_add(&self, dt: NaiveDateTime) -> NaiveDateTime {
+    fn apply_add<Tz: TimeZone>(
+        &self,
+        dt: &JalaliDateTime<Tz>,
+    ) -> Result<JalaliDateTime<Tz>, String> {
         match self.unit {
-            TimeUnit::Year => {
-                let mut new_year = dt.year() + self.value as i32;
-                let mut new_month = dt.month();
-                let mut new_day = dt.day();
-
-                // Handle month/day overflow
-                while new_month > 12 {
-                    new_year += 1;
-                    new_month -= 12;
-                }
-                while new_month < 1 {
-                    new_year -= 1;
-                    new_month += 12;
-                }
-
-                // Adjust day if it's invalid for the new month
-                let days_in_month = if new_month == 2 {
-                    if is_leap(new_year) { 29 } else { 28 }
-                } else if [4, 6, 9, 11].contains(&new_month) {
-                    30
-                } else {
-                    31
-                };
-                new_day = new_day.min(days_in_month);
-
-                NaiveDateTime::new(
-                    NaiveDate::from_ymd_opt(new_year, new_month, new_day).unwrap(),
-                    dt.time(),
-                )
-            }
-            TimeUnit::Month => {
-                let mut new_year = dt.year();
-                let mut new_month = dt.month() as i32 + self.value as i32;
-                let mut new_day = dt.day();
-
-                // Handle month overflow
-                while new_month > 12 {
-                    new_year += 1;
-                    new_month -= 12;
-                }
-                while new_month < 1 {
-                    new_year -= 1;
-                    new_month += 12;
-                }
-
-                // Adjust day if it's invalid for the new month
-                let days_in_month = if new_month == 2 {
-                    if is_leap(new_year) { 29 } else { 28 }
-                } else if [4, 6, 9, 11].contains(&new_month) {
-                    30
-                } else {
-                    31
-                };
-                new_day = new_day.min(days_in_month);
-
-                NaiveDateTime::new(
-                    NaiveDate::from_ymd_opt(new_year, new_month as u32, new_day).unwrap(),
-                    dt.time(),
-                )
-            }
-            TimeUnit::Week => dt + Duration::weeks(self.value),
-            TimeUnit::Day => dt + Duration::days(self.value),
-            TimeUnit::Hour => dt + Duration::hours(self.value),
-            TimeUnit::Minute => dt + Duration::minutes(self.value),
-            TimeUnit::Second => dt + Duration::seconds(self.value),
+            // Years and months move on the Jalali fields, so +1m from
+            // 30 Farvardin is 30 Ordibehesht; short months clamp the day.
+            TimeUnit::Year => dt
+                .date()
+                .checked_add_years(self.value, MonthEndPolicy::Clamp)
+                .and_then(|date| dt.with_date(date, DstPolicy::Compatible))
+                .map_err(|e| format!("Year adjustment {} failed: {}", self.value, e)),
+            TimeUnit::Month => dt
+                .date()
+                .checked_add_months(self.value, MonthEndPolicy::Clamp)
+                .and_then(|date| dt.with_date(date, DstPolicy::Compatible))
+                .map_err(|e| format!("Month adjustment {} failed: {}", self.value, e)),
+            // Weeks and days keep the wall-clock time across DST changes.
+            TimeUnit::Week => self
+                .value
+                .checked_mul(7)
+                .ok_or(JalaliError::Overflow)
+                .and_then(|days| dt.date().checked_add_days(days))
+                .and_then(|date| dt.with_date(date, DstPolicy::Compatible))
+                .map_err(|_| format!("Week adjustment {} is out of range", self.value)),
+            TimeUnit::Day => dt
+                .date()
+                .checked_add_days(self.value)
+                .and_then(|date| dt.with_date(date, DstPolicy::Compatible))
+                .map_err(|_| format!("Day adjustment {} is out of range", self.value)),
+            // Hours, minutes and seconds are elapsed time.
+            TimeUnit::Hour => TimeDelta::try_hours(self.value)
+                .ok_or(JalaliError::Overflow)
+                .and_then(|d| dt.checked_add_signed(d))
+                .map_err(|_| format!("Hour adjustment {} is out of range", self.value)),
+            TimeUnit::Minute => TimeDelta::try_minutes(self.value)
+                .ok_or(JalaliError::Overflow)
+                .and_then(|d| dt.checked_add_signed(d))
+                .map_err(|_| format!("Minute adjustment {} is out of range", self.value)),
+            TimeUnit::Second => TimeDelta::try_seconds(self.value)
+                .ok_or(JalaliError::Overflow)
+                .and_then(|d| dt.checked_add_signed(d))
+                .map_err(|_| format!("Second adjustment {} is out of range", self.value)),
         }
     }
 
-    fn apply_set(&self, dt: NaiveDateTime) -> NaiveDateTime {
-        let (jy, jm, jd) = gregorian_to_jalali(dt.year(), dt.month(), dt.day());
-        let (new_jy, new_jm, new_jd) = match self.unit {
+    fn apply_set<Tz: TimeZone>(
+        &self,
+        dt: &JalaliDateTime<Tz>,
+        era: Era,
+    ) -> Result<JalaliDateTime<Tz>, String> {
+        let date = dt.date();
+        let (jy, jm) = (date.year(), date.month());
+        let new_date = match self.unit {
             TimeUnit::Year => {
-                let new_jy = self.value as i32;
-                (new_jy, jm, jd)
+                let new_jy = i32::try_from(self.value).map_err(|_| {
+                    format!("Invalid Jalali year: {}. Example: 1379/08/15", self.value)
+                })?;
+                JalaliDate::try_from_ymd(era.year_to_solar_hijri(new_jy), jm, date.day())
             }
             TimeUnit::Month => {
-                let new_jm = self.value as u8;
-                if !(1..=12).contains(&new_jm) {
-                    panic!(
-                        "Error: Invalid Jalali month: {}. Example: 1379/08/15",
-                        new_jm
-                    );
-                }
-                (jy, new_jm, jd)
+                let Some(new_jm) = u8::try_from(self.value)
+                    .ok()
+                    .filter(|month| (1..=12).contains(month))
+                else {
+                    return Err(format!(
+                        "Invalid Jalali month: {}. Example: 1379/08/15",
+                        self.value
+                    ));
+                };
+                JalaliDate::try_from_ymd(jy, new_jm, date.day())
             }
             TimeUnit::Day => {
-                let new_jd = self.value as u8;
-                if new_jd < 1 || new_jd > days_in_month(jy, jm) {
-                    panic!(
-                        "Error: Invalid Jalali day: {} for month {}. Example: 1379/08/15",
-                        new_jd, jm
-                    );
-                }
-                (jy, jm, new_jd)
+                let Some(new_jd) = u8::try_from(self.value)
+                    .ok()
+                    .filter(|&day| day >= 1 && day <= days_in_month(jy, jm))
+                else {
+                    return Err(format!(
+                        "Invalid Jalali day: {} for month {}. Example: 1379/08/15",
+                        self.value, jm
+                    ));
+                };
+                JalaliDate::try_from_ymd(jy, jm, new_jd)
             }
             TimeUnit::Hour => {
-                let (gy, gm, gd) = jalali_to_gregorian(jy, jm, jd);
-                let new_dt =
-                    NaiveDateTime::new(NaiveDate::from_ymd_opt(gy, gm, gd).unwrap(), dt.time())
-                        .with_hour(self.value as u32)
-                        .unwrap();
-                return new_dt;
+                return u32::try_from(self.value)
+                    .ok()
+                    .and_then(|value| dt.time().with_hour(value))
+                    .ok_or_else(|| format!("Invalid hour: {}. Example: 14", self.value))
+                    .and_then(|time| {
+                        dt.with_time(time, DstPolicy::Compatible)
+                            .map_err(|e| e.to_string())
+                    });
             }
             TimeUnit::Minute => {
-                let (gy, gm, gd) = jalali_to_gregorian(jy, jm, jd);
-                let new_dt =
-                    NaiveDateTime::new(NaiveDate::from_ymd_opt(gy, gm, gd).unwrap(), dt.time())
-                        .with_minute(self.value as u32)
-                        .unwrap();
-                return new_dt;
+                return u32::try_from(self.value)
+                    .ok()
+                    .and_then(|value| dt.time().with_minute(value))
+                    .ok_or_else(|| format!("Invalid minute: {}. Example: 30", self.value))
+                    .and_then(|time| {
+                        dt.with_time(time, DstPolicy::Compatible)
+                            .map_err(|e| e.to_string())
+                    });
             }
             TimeUnit::Second => {
-                let (gy, gm, gd) = jalali_to_gregorian(jy, jm, jd);
-                let new_dt =
-                    NaiveDateTime::new(NaiveDate::from_ymd_opt(gy, gm, gd).unwrap(), dt.time())
-                        .with_second(self.value as u32)
-                        .unwrap();
-                return new_dt;
+                return u32::try_from(self.value)
+                    .ok()
+                    .and_then(|value| dt.time().with_second(value))
+                    .ok_or_else(|| format!("Invalid second: {}. Example: 45", self.value))
+                    .and_then(|time| {
+                        dt.with_time(time, DstPolicy::Compatible)
+                            .map_err(|e| e.to_string())
+                    });
             }
             TimeUnit::Week => {
-                // For week, we'll set to the first day of the specified week
-                let week_start = (self.value as i32 - 1) * 7 + 1;
-                if !(1..=365).contains(&week_start) {
-                    panic!("Error: Invalid week number: {}. Example: 42", self.value);
-                }
-                let mut day_count = 0;
-                let mut new_jm = 1;
-                let mut new_jd = 1;
-
-                // Find the month and day for the given week start
-                for m in 1..=12 {
-                    let days = days_in_month(jy, m) as i32;
-                    if day_count + days >= week_start {
-                        new_jm = m;
-                        new_jd = (week_start - day_count) as u8;
-                        break;
-                    }
-                    day_count += days;
-                }
-                (jy, new_jm, new_jd)
+                // Start of week N (Saturday-based, week 1 holds Nowruz) of
+                // the current week-based year.
+                let rule = WeekRule::default();
+                let week = u32::try_from(self.value)
+                    .map_err(|_| format!("Invalid week number: {}. Example: 42", self.value))?;
+                rule.date_from_week(date.week_of_year(rule).year(), week, rule.week_start())
             }
         };
 
-        let (gy, gm, gd) = jalali_to_gregorian(new_jy, new_jm, new_jd);
-        NaiveDateTime::new(NaiveDate::from_ymd_opt(gy, gm, gd).unwrap(), dt.time())
+        new_date
+            .and_then(|date| dt.with_date(date, DstPolicy::Compatible))
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -267,6 +254,26 @@ struct Cli {
     )]
     timezone: Option<String>,
 
+    // Locale of month and weekday names
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help = "Locale of names: latin, fa, prs, prs-latn, ps or ckb"
+    )]
+    locale: Option<Locale>,
+
+    // Era of the years read and printed
+    #[arg(
+        long,
+        value_name = "ERA",
+        help = "Era of years: solar-hijri, imperial or yazdgerdi"
+    )]
+    era: Option<Era>,
+
+    // Long written date, e.g. for letters
+    #[arg(short = 'W', long, help = "Spell out the date in words")]
+    written: bool,
+
     // RFC 2822 format
     #[arg(short = 'R', long, help = "Output in RFC 2822 format")]
     rfc2822: bool,
@@ -290,36 +297,40 @@ struct Cli {
         help = "Adjust date/time"
     )]
     adjustments: Vec<String>,
-}
 
-// Parse YYYY/MM/DD or YYYY-MM-DD
-fn parse_date(date_str: &str, is_jalali_context: bool) -> Result<(i32, u32, u32), String> {
-    let example_date_str = if is_jalali_context {
-        "1379/08/15"
-    } else {
-        "2000/11/05"
-    };
-    let example_year_str = if is_jalali_context { "1379" } else { "2000" };
-    let example_month_str = if is_jalali_context { "08" } else { "11" };
-    let example_day_str = if is_jalali_context { "15" } else { "05" };
+    // Show Tahvil-e Sal instead of the current time
+    #[arg(
+        short = 'E',
+        long,
+        value_name = "YEAR",
+        value_parser = parse_number::<i32>,
+        help = "Show Tahvil-e Sal (March equinox) of a Jalali year"
+    )]
+    equinox: Option<Option<i32>>,
 
+    // Difference between two Jalali dates
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["FROM", "TO"],
+        help = "Show the difference between two Jalali dates"
+    )]
+    diff: Option<Vec<String>>,
+}
+
+// Parse a Gregorian YYYY/MM/DD or YYYY-MM-DD
+fn parse_date(date_str: &str) -> Result<(i32, u32, u32), String> {
     let parts: Vec<&str> = date_str.split(['/', '-']).collect();
     if parts.len() != 3 {
-        return Err(
-            "Error: Invalid date format. Use YYYY/MM/DD. Example: 2000/11/05 or 1379/08/15"
-                .to_string(),
-        );
+        return Err("Error: Invalid date format. Use YYYY/MM/DD. Example: 2000/11/05".to_string());
     }
 
-    let year = parts[0]
-        .parse::<i32>()
-        .map_err(|_| format!("Error: Invalid year. Example: {}", example_year_str))?;
-    let month = parts[1]
-        .parse::<u32>()
-        .map_err(|_| format!("Error: Invalid month. Example: {}", example_month_str))?;
-    let day = parts[2]
-        .parse::<u32>()
-        .map_err(|_| format!("Error: Invalid day. Example: {}", example_day_str))?;
+    let year = parse_number::<i32>(parts[0])
+        .map_err(|_| "Error: Invalid year. Example: 2000".to_string())?;
+    let month = parse_number::<u32>(parts[1])
+        .map_err(|_| "Error: Invalid month. Example: 11".to_string())?;
+    let day =
+        parse_number::<u32>(parts[2]).map_err(|_| "Error: Invalid day. Example: 05".to_string())?;
 
     if month == 0
         || month > MONTHS_PER_YEAR_COUNT as u32
@@ -327,143 +338,165 @@ fn parse_date(date_str: &str, is_jalali_context: bool) -> Result<(i32, u32, u32)
         || day > MAX_DAYS_IN_GREGORIAN_MONTH as u32
     // Max 31 days is a safe general check here
     {
-        return Err(format!(
-            "Error: Invalid month or day value. Month should be 1-12, Day should be 1-31 (approx). Example: {}",
-            example_date_str
-        ));
+        return Err(
+            "Error: Invalid month or day value. Month should be 1-12, Day should be 1-31 (approx). Example: 2000/11/05"
+                .to_string(),
+        );
     }
 
     Ok((year, month, day))
 }
 
-fn format_rfc2822(dt: NaiveDateTime, jy: i32, jm: u8, jd: u8, offset_str: &str) -> String {
-    let weekday_idx = (dt.weekday().num_days_from_sunday() + 1) % 7;
-    let jalali_weekday_abbr = JALALI_WEEKDAYS_ABBR_ALT[weekday_idx as usize];
-    let month_name = MONTH_NAMES[(jm - 1) as usize];
-    format!(
-        "{}, {} {} {} {:02}:{:02}:{:02} {}",
-        jalali_weekday_abbr,
-        jd,
-        month_name,
-        jy,
-        dt.hour(),
-        dt.minute(),
-        dt.second(),
-        offset_str
-    )
+// Jalali ISO 8601 pattern for `-I[=PRECISION]`.
+fn iso8601_pattern(precision: Option<&str>) -> &'static str {
+    match precision {
+        Some("hours") => "%Y-%m-%dT%H",
+        Some("minutes") => "%Y-%m-%dT%H:%M",
+        Some("seconds") => "%Y-%m-%dT%T",
+        _ => "%F",
+    }
 }
 
-fn format_iso8601(dt: NaiveDateTime, jy: i32, jm: u8, jd: u8, precision: Option<&str>) -> String {
-    match precision {
-        Some("hours") => {
-            let offset = if dt.hour() >= 24 {
-                format!("+{:02}", dt.hour() / 24)
-            } else {
-                format!("{:02}", dt.hour())
-            };
-            format!("{:04}-{:02}-{:02}T{}", jy, jm, jd, offset)
-        }
-        Some("minutes") => {
-            let offset = if dt.hour() >= 24 {
-                format!("+{:02}:{:02}", dt.hour() / 24, dt.minute())
-            } else {
-                format!("{:02}:{:02}", dt.hour(), dt.minute())
-            };
-            format!("{:04}-{:02}-{:02}T{}", jy, jm, jd, offset)
+// Applies the `-v` adjustments in order, with set years counted in
+// `input_era`, then prints with `pattern`.
+fn print_adjusted<Tz: TimeZone>(
+    now: DateTime<Tz>,
+    adjustments: &[String],
+    input_era: Era,
+    pattern: &str,
+    locale: Locale,
+    era: Era,
+) where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut dt = match JalaliDateTime::from_datetime(now) {
+        Ok(dt) => dt,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
         }
-        Some("seconds") => {
-            let offset = if dt.hour() >= 24 {
-                format!(
-                    "+{:02}:{:02}:{:02}",
-                    dt.hour() / 24,
-                    dt.minute(),
-                    dt.second()
-                )
-            } else {
-                format!("{:02}:{:02}:{:02}", dt.hour(), dt.minute(), dt.second())
-            };
-            format!("{:04}-{:02}-{:02}T{}", jy, jm, jd, offset)
+    };
+    for adj_str in adjustments {
+        match TimeAdjustment::parse(adj_str) {
+            Ok(adj) => match adj.apply(&dt, input_era) {
+                Ok(new_dt) => dt = new_dt,
+                Err(e) => {
+                    eprintln!("Error: Could not apply adjustment \'{}\': {}", adj_str, e);
+                    process::exit(1);
+                }
+            },
+            Err(e) => {
+                eprintln!(
+                    "Error: Could not parse adjustment string \'{}\': {}. Example: +5d or -2m",
+                    adj_str, e
+                );
+                process::exit(1);
+            }
         }
-        _ => format!("{:04}-{:02}-{:02}", jy, jm, jd),
     }
+    println!("{}", dt.format(pattern).locale(locale).era(era));
 }
 
 fn main() {
     let cli = Cli::parse();
+    let locale = cli.locale.unwrap_or_else(Locale::from_env);
+    let era = cli.era.unwrap_or_default();
 
-    match (cli.jalali_to_gregorian, cli.gregorian_to_jalali) {
-        (Some(jdate_str), None) => {
-            // Jalali to Gregorian (-g)
-            match parse_date(&jdate_str, true) {
-                Ok((jy, jm, jd)) => {
-                    if jd > days_in_month(jy, jm as u8) as u32 {
-                        eprintln!(
-                            "Error: Invalid day {} for month {} in year {}. Example: 1379/08/15",
-                            jd, jm, jy
-                        );
-                        process::exit(1);
-                    }
-                    let (gy, gm, gd) = jalali_to_gregorian(jy, jm as u8, jd as u8);
-
-                    match NaiveDate::from_ymd_opt(gy, gm, gd) {
-                        Some(naive_date) => {
-                            let datetime = naive_date.and_hms_opt(0, 0, 0).unwrap();
-                            // We'll format manually to match.
-                            let weekday = GREGORIAN_WEEKDAYS_ABBR
-                                [datetime.weekday().num_days_from_monday() as usize];
-                            let month_abbr = GREGORIAN_MONTH_ABBRS[(gm - 1) as usize];
-                            println!(
-                                "{} {} {:02} {:02}:{:02}:{:02} UTC {}",
-                                weekday,
-                                month_abbr,
-                                gd,
-                                datetime.hour(),
-                                datetime.minute(),
-                                datetime.second(),
-                                gy
-                            );
-                        }
-                        None => {
-                            eprintln!(
-                                "Error: Calculated Gregorian date ({}-{}-{}) is invalid. Example: 2000/11/05",
-                                gy, gm, gd
-                            );
-                            process::exit(1);
-                        }
-                    }
-                }
+    if cli.equinox.is_some()
+        && (cli.jalali_to_gregorian.is_some() || cli.gregorian_to_jalali.is_some())
+    {
+        eprintln!(
+            "Error: The -E (equinox) option cannot be combined with -g or -j. Example: sdate -E 1404 -z Asia/Tehran"
+        );
+        process::exit(1);
+    }
+
+    if cli.written
+        && (cli.rfc2822
+            || cli.iso8601.is_some()
+            || cli.jalali_to_gregorian.is_some()
+            || cli.diff.is_some())
+    {
+        eprintln!(
+            "Error: The -W (written) option cannot be combined with -R, -I, -g or --diff. Example: sdate -W --locale fa"
+        );
+        process::exit(1);
+    }
+    let default_pattern = if cli.written { "%~D" } else { "%c" };
+
+    if let Some(dates) = &cli.diff {
+        if cli.equinox.is_some()
+            || cli.jalali_to_gregorian.is_some()
+            || cli.gregorian_to_jalali.is_some()
+        {
+            eprintln!(
+                "Error: The --diff option cannot be combined with -g, -j or -E. Example: sdate --diff 1399/12/30 1403/05/10"
+            );
+            process::exit(1);
+        }
+        let mut parsed = Vec::with_capacity(dates.len());
+        for date_str in dates {
+            let date_str = normalize_persian(date_str);
+            match JalaliParsed::parse_any_with_era(&date_str, &COMMON_JALALI_PATTERNS[..2], era) {
+                Ok(date) => parsed.push(date.date()),
                 Err(e) => {
-                    eprintln!("Error: {}", e);
+                    eprintln!("Error: {}. Example: sdate --diff 1399/12/30 1403/05/10", e);
                     process::exit(1);
                 }
             }
         }
+        let period = JalaliPeriod::between(parsed[0], parsed[1]);
+        println!("{} days ({})", period.total_days(), period);
+        return;
+    }
+
+    match (cli.jalali_to_gregorian, cli.gregorian_to_jalali) {
+        (Some(jdate_str), None) => {
+            // Jalali to Gregorian (-g); accepts any of `COMMON_JALALI_PATTERNS`
+            let jdate_str = normalize_persian(&jdate_str);
+            let parsed =
+                match JalaliParsed::parse_any_with_era(&jdate_str, &COMMON_JALALI_PATTERNS, era) {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        eprintln!("Error: {}. Example: 1379/08/15 or \"15 Aban 1379\"", e);
+                        process::exit(1);
+                    }
+                };
+            let gregorian = parsed.date().to_gregorian();
+            let datetime = gregorian.and_time(parsed.time().unwrap_or(NaiveTime::MIN));
+            let zone = parsed
+                .offset()
+                .map_or_else(|| "UTC".to_string(), |offset| offset.to_string());
+            // We'll format manually to match.
+            let weekday =
+                GREGORIAN_WEEKDAYS_ABBR[datetime.weekday().num_days_from_monday() as usize];
+            let month_abbr = GREGORIAN_MONTH_ABBRS[gregorian.month0() as usize];
+            println!(
+                "{} {} {:02} {:02}:{:02}:{:02} {} {}",
+                weekday,
+                month_abbr,
+                gregorian.day(),
+                datetime.hour(),
+                datetime.minute(),
+                datetime.second(),
+                zone,
+                gregorian.year()
+            );
+        }
         (None, Some(gdate_str)) => {
             // Gregorian to Jalali (-j)
-            match parse_date(&gdate_str, false) {
+            match parse_date(&gdate_str) {
                 Ok((gy, gm, gd)) => {
-                    if NaiveDate::from_ymd_opt(gy, gm, gd).is_none() {
+                    let Some(gregorian) = NaiveDate::from_ymd_opt(gy, gm, gd) else {
                         eprintln!("Error: Invalid Gregorian date specified. Example: 2000/11/05");
                         process::exit(1);
-                    }
-                    let (jy, jm, jd) = gregorian_to_jalali(gy, gm, gd);
-                    let (g_conv_y, g_conv_m, g_conv_d) = jalali_to_gregorian(jy, jm, jd);
-
-                    match NaiveDate::from_ymd_opt(g_conv_y, g_conv_m, g_conv_d) {
-                        Some(naive_date) => {
-                            let weekday_idx = (naive_date.weekday().num_days_from_sunday() + 1) % 7;
-                            let jalali_weekday_abbr =
-                                JALALI_WEEKDAYS_ABBR_ALT[weekday_idx as usize];
-                            let month_name = MONTH_NAMES[(jm - 1) as usize];
-                            println!(
-                                "{} {} {:02} 00:00:00 UTC {}",
-                                jalali_weekday_abbr, month_name, jd, jy
-                            );
+                    };
+                    match JalaliDate::try_from(gregorian) {
+                        Ok(date) => {
+                            println!("{}", date.format(default_pattern).locale(locale).era(era))
                         }
-                        None => {
-                            eprintln!(
-                                "Error: Internal conversion resulted in invalid date. Example: 1379/08/15"
-                            );
+                        Err(e) => {
+                            eprintln!("Error: {}. Example: 2000/11/05", e);
                             process::exit(1);
                         }
                     }
@@ -475,13 +508,52 @@ fn main() {
             }
         }
         (None, None) => {
-            // No flags: print current date in Jalali
-            let (naive_local, offset_str) = if let Some(tz_str) = &cli.timezone {
-                match tz_str.parse::<Tz>() {
-                    Ok(tz) => {
-                        let tz_now = tz.from_utc_datetime(&Utc::now().naive_utc());
-                        (tz_now.naive_local(), tz_now.offset().to_string())
+            // No flags: print current date in Jalali, or Tahvil-e Sal with -E
+            let instant = match cli.equinox {
+                Some(year) => {
+                    let jy = year
+                        .map(|year| era.year_to_solar_hijri(year))
+                        .unwrap_or_else(|| {
+                            JalaliDate::try_from(Local::now())
+                                .expect("today is within the supported Jalali range")
+                                .year()
+                        });
+                    match vernal_equinox_utc(jy) {
+                        Ok(instant) => instant,
+                        Err(e) => {
+                            eprintln!(
+                                "Error: Cannot compute Tahvil-e Sal for year {}: {}. Example: sdate -E 1404",
+                                jy, e
+                            );
+                            process::exit(1);
+                        }
                     }
+                }
+                None => Utc::now(),
+            };
+            // RFC 2822 and ISO 8601 output is for machines, so stays Latin and
+            // Solar Hijri.
+            let (pattern, out_locale, out_era) = if cli.rfc2822 {
+                ("%a, %-d %B %-Y %T %Z", Locale::Latin, Era::SolarHijri)
+            } else if let Some(precision) = &cli.iso8601 {
+                (
+                    iso8601_pattern(precision.as_deref()),
+                    Locale::Latin,
+                    Era::SolarHijri,
+                )
+            } else {
+                (default_pattern, locale, era)
+            };
+            if let Some(tz_str) = &cli.timezone {
+                match tz_str.parse::<Tz>() {
+                    Ok(tz) => print_adjusted(
+                        instant.with_timezone(&tz),
+                        &cli.adjustments,
+                        era,
+                        pattern,
+                        out_locale,
+                        out_era,
+                    ),
                     Err(_) => {
                         eprintln!(
                             "Error: Invalid timezone \'{}\'. Example: America/New_York",
@@ -491,58 +563,15 @@ fn main() {
                     }
                 }
             } else if cli.utc {
-                let utc_now = Utc::now();
-                (utc_now.naive_local(), "UTC".to_string())
-            } else {
-                let local_now = Local::now();
-                (local_now.naive_local(), local_now.offset().to_string())
-            };
-
-            // Apply time adjustments if any
-            let adjusted_dt = if !cli.adjustments.is_empty() {
-                let mut dt = naive_local;
-                for adj_str in &cli.adjustments {
-                    match TimeAdjustment::parse(adj_str) {
-                        Ok(adj) => dt = adj.apply(dt),
-                        Err(e) => {
-                            eprintln!(
-                                "Error: Could not parse adjustment string \'{}\': {}. Example: +5d or -2m",
-                                adj_str, e
-                            );
-                            process::exit(1);
-                        }
-                    }
-                }
-                dt
-            } else {
-                naive_local
-            };
-
-            let (jy, jm, jd) =
-                gregorian_to_jalali(adjusted_dt.year(), adjusted_dt.month(), adjusted_dt.day());
-
-            if cli.rfc2822 {
-                println!("{}", format_rfc2822(adjusted_dt, jy, jm, jd, &offset_str));
-            } else if let Some(precision) = &cli.iso8601 {
-                println!(
-                    "{}",
-                    format_iso8601(adjusted_dt, jy, jm, jd, precision.as_deref())
-                );
+                print_adjusted(instant, &cli.adjustments, era, pattern, out_locale, out_era);
             } else {
-                let weekday_idx = (adjusted_dt.weekday().num_days_from_sunday() + 1) % 7;
-                let jalali_weekday_abbr = JALALI_WEEKDAYS_ABBR_ALT[weekday_idx as usize];
-                let month_name = MONTH_NAMES[(jm - 1) as usize];
-
-                println!(
-                    "{} {} {:02} {:02}:{:02}:{:02} {} {}",
-                    jalali_weekday_abbr,
-                    month_name,
-                    jd,
-                    adjusted_dt.hour(),
-                    adjusted_dt.minute(),
-                    adjusted_dt.second(),
-                    offset_str,
-                    jy
+                print_adjusted(
+                    instant.with_timezone(&Local),
+                    &cli.adjustments,
+                    era,
+                    pattern,
+                    out_locale,
+                    out_era,
                 );
             }
         }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Utc};
+use chrono::{DateTime, Local, TimeZone};
 use clap::Parser;
 use scal_lib::*;
 use std::fs;
@@ -20,6 +20,18 @@ struct Cli {
     paths: Vec<String>,
     #[arg(short = 'p', long, help = "Display Farsi numbers and names")]
     persian_output: bool,
+    #[arg(
+        long,
+        value_name = "LOCALE",
+        help = "Locale of names: latin, fa, prs, prs-latn, ps or ckb"
+    )]
+    locale: Option<Locale>,
+    #[arg(
+        long,
+        value_name = "ERA",
+        help = "Era of years: solar-hijri, imperial or yazdgerdi"
+    )]
+    era: Option<Era>,
     #[arg(short = 'l', long, help = "Display in ls -lT format")]
     ls_format: bool,
     #[arg(short = 'n', long, help = "Do not print trailing newline")]
@@ -58,6 +70,19 @@ fn main() {
         process::exit(1);
     }
 
+    if cli.persian_output && cli.locale.is_some() {
+        eprintln!(
+            "Error: The -p (Persian output) and --locale options cannot be used together. Example: sstat --locale prs /path/to/file"
+        );
+        process::exit(1);
+    }
+    let locale = if cli.persian_output {
+        Locale::Persian
+    } else {
+        cli.locale.unwrap_or_else(Locale::from_env)
+    };
+    let era = cli.era.unwrap_or_default();
+
     let num_str = |num: String, persian: bool| -> String {
         if persian {
             to_persian_numerals(&num)
@@ -105,25 +130,16 @@ fn main() {
                     })
                     .unwrap_or(metadata.ctime());
 
-                let mut atime_local: DateTime<Local> =
+                let atime_local: DateTime<Local> =
                     DateTime::from(metadata.accessed().unwrap_or(SystemTime::UNIX_EPOCH));
-                let mut mtime_local: DateTime<Local> =
+                let mtime_local: DateTime<Local> =
                     DateTime::from(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
-                let mut btime_local: DateTime<Local> =
+                let btime_local: DateTime<Local> =
                     DateTime::from(metadata.created().unwrap_or(SystemTime::UNIX_EPOCH));
-                let ctime_utc_for_local = Utc
-                    .timestamp_opt(ctime_raw_sec, 0)
+                let ctime_local: DateTime<Local> = Local
+                    .timestamp_opt(ctime_raw_sec, metadata.ctime_nsec() as u32)
                     .single()
                     .unwrap_or_default();
-                let mut ctime_local: DateTime<Local> = ctime_utc_for_local.with_timezone(&Local);
-
-                if !cli.raw_format && !cli.shell_format {
-                    let correction = Duration::minutes(90); // FIX BUG :/
-                    atime_local += correction;
-                    mtime_local += correction;
-                    btime_local += correction;
-                    ctime_local += correction;
-                }
 
                 let user_name = users::get_user_by_uid(uid)
                     .map(|u| u.name().to_string_lossy().into_owned())
@@ -134,6 +150,7 @@ fn main() {
 
                 let mode_str_permissions = format_mode(mode);
 
+                // Shell and raw output are for scripts, so only -p changes their digits.
                 if cli.shell_format {
                     print!(
                         "st_dev={} st_ino={} st_mode={} st_nlink={} st_uid={} st_gid={} st_rdev={} st_size={} st_atime={} st_mtime={} st_ctime={} st_birthtime={} st_blksize={} st_blocks={} st_flags={}",
@@ -219,113 +236,61 @@ fn main() {
                     print!(
                         "{} {:>3} {} {} {:>7} {} {}",
                         mode_str_permissions,
-                        num_str(nlink.to_string(), cli.persian_output),
+                        num_str(nlink.to_string(), locale.native_digits()),
                         user_name,
                         group_name,
-                        num_str(size.to_string(), cli.persian_output),
+                        num_str(size.to_string(), locale.native_digits()),
                         ls_time_format,
                         path_str
                     );
                 } else {
                     // Default Shamsi (Jalali) format
-                    let (jy_atime, jm_atime, jd_atime) = gregorian_to_jalali(
-                        atime_local.year(),
-                        atime_local.month(),
-                        atime_local.day(),
-                    );
-                    let (jy_mtime, jm_mtime, jd_mtime) = gregorian_to_jalali(
-                        mtime_local.year(),
-                        mtime_local.month(),
-                        mtime_local.day(),
-                    );
-                    let (jy_ctime, jm_ctime, jd_ctime) = gregorian_to_jalali(
-                        ctime_local.year(),
-                        ctime_local.month(),
-                        ctime_local.day(),
-                    );
-                    let (jy_btime, jm_btime, jd_btime) = gregorian_to_jalali(
-                        btime_local.year(),
-                        btime_local.month(),
-                        btime_local.day(),
-                    );
-
-                    let format_jalali_datetime = |jy: i32,
-                                                  jm: u8,
-                                                  jd: u8,
-                                                  dt_local: &DateTime<Local>,
-                                                  persian_output: bool|
-                     -> String {
-                        let month_name = if persian_output {
-                            PERSIAN_MONTH_NAMES[(jm - 1) as usize]
-                        } else {
-                            MONTH_NAMES[(jm - 1) as usize]
-                        };
-                        let year_str = if persian_output {
-                            to_persian_numerals(&jy.to_string())
-                        } else {
-                            jy.to_string()
-                        };
-                        let day_str = if persian_output {
-                            to_persian_numerals(&jd.to_string())
-                        } else {
-                            jd.to_string()
-                        };
-                        let time_val = dt_local.time();
-                        let time_str = time_val.format("%H:%M:%S").to_string();
-                        let time_str_final = if persian_output {
-                            to_persian_numerals(&time_str)
-                        } else {
-                            time_str
-                        };
-                        format!("{} {} {} {}", month_name, day_str, time_str_final, year_str)
+                    let format_jalali_datetime = |dt_local: &DateTime<Local>| {
+                        JalaliDateTime::from_datetime(*dt_local).map(|dt| {
+                            dt.format("%B %-d %T %-Y")
+                                .locale(locale)
+                                .era(era)
+                                .to_string()
+                        })
                     };
-
-                    let atime_jalali_str = format_jalali_datetime(
-                        jy_atime,
-                        jm_atime,
-                        jd_atime,
-                        &atime_local,
-                        cli.persian_output,
-                    );
-                    let mtime_jalali_str = format_jalali_datetime(
-                        jy_mtime,
-                        jm_mtime,
-                        jd_mtime,
-                        &mtime_local,
-                        cli.persian_output,
-                    );
-                    let ctime_jalali_str = format_jalali_datetime(
-                        jy_ctime,
-                        jm_ctime,
-                        jd_ctime,
-                        &ctime_local,
-                        cli.persian_output,
-                    );
-                    let btime_jalali_str = format_jalali_datetime(
-                        jy_btime,
-                        jm_btime,
-                        jd_btime,
-                        &btime_local,
-                        cli.persian_output,
-                    );
+                    let jalali_times = format_jalali_datetime(&atime_local).and_then(|atime| {
+                        Ok((
+                            atime,
+                            format_jalali_datetime(&mtime_local)?,
+                            format_jalali_datetime(&ctime_local)?,
+                            format_jalali_datetime(&btime_local)?,
+                        ))
+                    });
+                    let (atime_jalali_str, mtime_jalali_str, ctime_jalali_str, btime_jalali_str) =
+                        match jalali_times {
+                            Ok(times) => times,
+                            Err(e) => {
+                                eprintln!(
+                                    "Error: Cannot convert timestamps of \'{}\' to Jalali: {}",
+                                    path_str, e
+                                );
+                                overall_exit_code = 1;
+                                continue;
+                            }
+                        };
 
                     print!(
                         "{} {} {} {} {} {} {} {} \"{}\" \"{}\" \"{}\" \"{}\" {} {} {} {}",
-                        num_str(device_id.to_string(), cli.persian_output),
-                        num_str(inode.to_string(), cli.persian_output),
+                        num_str(device_id.to_string(), locale.native_digits()),
+                        num_str(inode.to_string(), locale.native_digits()),
                         mode_str_permissions,
-                        num_str(nlink.to_string(), cli.persian_output),
+                        num_str(nlink.to_string(), locale.native_digits()),
                         user_name,
                         group_name,
-                        num_str(rdev.to_string(), cli.persian_output),
-                        num_str(size.to_string(), cli.persian_output),
+                        num_str(rdev.to_string(), locale.native_digits()),
+                        num_str(size.to_string(), locale.native_digits()),
                         atime_jalali_str,
                         mtime_jalali_str,
                         ctime_jalali_str,
                         btime_jalali_str,
-                        num_str(blksize.to_string(), cli.persian_output),
-                        num_str(blocks.to_string(), cli.persian_output),
-                        num_str("0".to_string(), cli.persian_output),
+                        num_str(blksize.to_string(), locale.native_digits()),
+                        num_str(blocks.to_string(), locale.native_digits()),
+                        num_str("0".to_string(), locale.native_digits()),
                         path_str
                     );
                 }
@@ -0,0 +1,294 @@
+//! Business days under a configurable workweek and holiday calendar.
+//!
+//! The default is the Iranian week: Saturday to Wednesday are full working
+//! days, Thursday a half day and Friday the weekly day off, with the
+//! official holidays of `HolidayCalendar::iran` off as well.
+
+use chrono::Weekday;
+
+use crate::{
+    HolidayCalendar, JALALI_FRIDAY_INDEX, JalaliDate, JalaliError, WEEK_DAYS_TOTAL, first_weekday,
+};
+
+/// How much of a day is worked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WorkDay {
+    #[default]
+    Full,
+    /// A business day, but worth half a day in `worked_days_between`.
+    Half,
+    Off,
+}
+
+impl WorkDay {
+    /// 1, 0.5 or 0.
+    pub fn fraction(self) -> f64 {
+        match self {
+            WorkDay::Full => 1.0,
+            WorkDay::Half => 0.5,
+            WorkDay::Off => 0.0,
+        }
+    }
+}
+
+/// The working pattern of each day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Workweek {
+    // Saturday first, as the columns of `first_weekday`.
+    days: [WorkDay; WEEK_DAYS_TOTAL as usize],
+}
+
+impl Workweek {
+    /// Every day a full working day; turn days off with `with_day`.
+    pub const fn all_days() -> Workweek {
+        Workweek {
+            days: [WorkDay::Full; WEEK_DAYS_TOTAL as usize],
+        }
+    }
+
+    /// Friday off and Thursday a half day.
+    pub fn iran() -> Workweek {
+        let mut week = Workweek::all_days();
+        week.days[JALALI_FRIDAY_INDEX - 1] = WorkDay::Half;
+        week.days[JALALI_FRIDAY_INDEX] = WorkDay::Off;
+        week
+    }
+
+    pub fn with_day(mut self, weekday: Weekday, work: WorkDay) -> Workweek {
+        self.days[column(weekday)] = work;
+        self
+    }
+
+    pub fn day(&self, weekday: Weekday) -> WorkDay {
+        self.days[column(weekday)]
+    }
+
+    fn has_working_day(&self) -> bool {
+        self.days.iter().any(|&day| day != WorkDay::Off)
+    }
+}
+
+impl Default for Workweek {
+    fn default() -> Workweek {
+        Workweek::iran()
+    }
+}
+
+/// A workweek together with the holidays that are also days off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BusinessCalendar {
+    workweek: Workweek,
+    holidays: HolidayCalendar,
+}
+
+impl BusinessCalendar {
+    pub fn new(workweek: Workweek, holidays: HolidayCalendar) -> BusinessCalendar {
+        BusinessCalendar { workweek, holidays }
+    }
+
+    /// The Iranian workweek and the bundled official holidays.
+    pub fn iran() -> BusinessCalendar {
+        BusinessCalendar::new(Workweek::iran(), HolidayCalendar::iran())
+    }
+
+    pub fn workweek(&self) -> &Workweek {
+        &self.workweek
+    }
+
+    pub fn holidays(&self) -> &HolidayCalendar {
+        &self.holidays
+    }
+
+    /// `WorkDay::Off` on holidays, otherwise what the workweek says.
+    pub fn work_day(&self, date: JalaliDate) -> WorkDay {
+        let work = self.workweek.days[date_column(date)];
+        if work == WorkDay::Off || self.holidays.is_holiday(date) {
+            WorkDay::Off
+        } else {
+            work
+        }
+    }
+
+    /// Whether `date` is worked at all, as a full or half day.
+    pub fn is_business_day(&self, date: JalaliDate) -> bool {
+        self.work_day(date) != WorkDay::Off
+    }
+
+    /// The first business day after `date`.
+    pub fn next_business_day(&self, date: JalaliDate) -> Result<JalaliDate, JalaliError> {
+        self.step(date, 1)
+    }
+
+    /// The last business day before `date`.
+    pub fn previous_business_day(&self, date: JalaliDate) -> Result<JalaliDate, JalaliError> {
+        self.step(date, -1)
+    }
+
+    /// The date `days` business days after `date` (before it if negative),
+    /// e.g. a deadline of 10 working days. Zero gives `date` itself.
+    pub fn add_business_days(
+        &self,
+        date: JalaliDate,
+        days: i64,
+    ) -> Result<JalaliDate, JalaliError> {
+        let direction = days.signum();
+        let mut current = date;
+        for _ in 0..days.unsigned_abs() {
+            current = self.step(current, direction)?;
+        }
+        Ok(current)
+    }
+
+    /// Business days after `start` up to and including `end`, negative if
+    /// `end` is before `start`; `add_business_days(start, n)` lands on `end`
+    /// when `end` is a business day.
+    pub fn business_days_between(&self, start: JalaliDate, end: JalaliDate) -> i64 {
+        self.sum_between(start, end, |day| i64::from(day != WorkDay::Off))
+    }
+
+    /// Like `business_days_between`, with half days counted as 0.5.
+    pub fn worked_days_between(&self, start: JalaliDate, end: JalaliDate) -> f64 {
+        self.sum_between(start, end, WorkDay::fraction)
+    }
+
+    fn sum_between<T>(&self, start: JalaliDate, end: JalaliDate, value: impl Fn(WorkDay) -> T) -> T
+    where
+        T: std::iter::Sum + std::ops::Neg<Output = T>,
+    {
+        let (from, to, sign) = if start <= end {
+            (start, end, false)
+        } else {
+            (end, start, true)
+        };
+        let total: T = (from.to_jdn() + 1..=to.to_jdn())
+            .map(|jdn| {
+                let date = JalaliDate::try_from_jdn(jdn).expect("between two valid dates");
+                value(self.work_day(date))
+            })
+            .sum();
+        if sign { -total } else { total }
+    }
+
+    fn step(&self, date: JalaliDate, direction: i64) -> Result<JalaliDate, JalaliError> {
+        if !self.workweek.has_working_day() {
+            return Err(JalaliError::NoWorkingDays);
+        }
+        let mut current = date;
+        loop {
+            current = current.checked_add_days(direction)?;
+            if self.work_day(current) != WorkDay::Off {
+                return Ok(current);
+            }
+        }
+    }
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> BusinessCalendar {
+        BusinessCalendar::iran()
+    }
+}
+
+// Column (0 = Saturday ... 6 = Friday) of a weekday.
+fn column(weekday: Weekday) -> usize {
+    (weekday.num_days_from_sunday() as usize + 1) % WEEK_DAYS_TOTAL as usize
+}
+
+fn date_column(date: JalaliDate) -> usize {
+    let first = first_weekday(date.year(), date.month()).expect("the date is valid");
+    (first as usize + date.day() as usize - 1) % WEEK_DAYS_TOTAL as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_iranian_week() {
+        // 10 Mordad 1403 was a Wednesday.
+        let calendar = BusinessCalendar::iran();
+        assert_eq!(calendar.work_day(ymd(1403, 5, 10)), WorkDay::Full);
+        assert_eq!(calendar.work_day(ymd(1403, 5, 11)), WorkDay::Half);
+        assert!(!calendar.is_business_day(ymd(1403, 5, 12)));
+        assert_eq!(date_column(ymd(1403, 5, 12)), JALALI_FRIDAY_INDEX);
+        assert_eq!(Workweek::iran().day(Weekday::Thu), WorkDay::Half);
+        assert_eq!(Workweek::iran().day(Weekday::Fri), WorkDay::Off);
+
+        assert_eq!(
+            calendar.next_business_day(ymd(1403, 5, 11)),
+            Ok(ymd(1403, 5, 13))
+        );
+        assert_eq!(
+            calendar.previous_business_day(ymd(1403, 5, 13)),
+            Ok(ymd(1403, 5, 11))
+        );
+        assert_eq!(
+            calendar.add_business_days(ymd(1403, 5, 10), 3),
+            Ok(ymd(1403, 5, 14))
+        );
+        assert_eq!(
+            calendar.add_business_days(ymd(1403, 5, 14), -3),
+            Ok(ymd(1403, 5, 10))
+        );
+        assert_eq!(
+            calendar.add_business_days(ymd(1403, 5, 12), 0),
+            Ok(ymd(1403, 5, 12))
+        );
+        assert_eq!(
+            calendar.business_days_between(ymd(1403, 5, 10), ymd(1403, 5, 14)),
+            3
+        );
+        assert_eq!(
+            calendar.business_days_between(ymd(1403, 5, 14), ymd(1403, 5, 10)),
+            -3
+        );
+        assert_eq!(
+            calendar.worked_days_between(ymd(1403, 5, 10), ymd(1403, 5, 14)),
+            2.5
+        );
+    }
+
+    #[test]
+    fn test_holidays_and_custom_weeks() {
+        // 29 Esfand 1402 and 1-4 Farvardin 1403 are holidays.
+        let calendar = BusinessCalendar::iran();
+        assert_eq!(
+            calendar.next_business_day(ymd(1402, 12, 28)),
+            Ok(ymd(1403, 1, 5))
+        );
+        assert_eq!(
+            calendar.business_days_between(ymd(1402, 12, 28), ymd(1403, 1, 5)),
+            1
+        );
+
+        let five_days = BusinessCalendar::new(
+            Workweek::iran().with_day(Weekday::Thu, WorkDay::Off),
+            HolidayCalendar::parse("").unwrap(),
+        );
+        assert_eq!(
+            five_days.next_business_day(ymd(1403, 5, 10)),
+            Ok(ymd(1403, 5, 13))
+        );
+        assert_eq!(
+            five_days.worked_days_between(ymd(1403, 5, 5), ymd(1403, 5, 12)),
+            5.0
+        );
+
+        let no_work = BusinessCalendar::new(
+            Workweek::all_days()
+                .with_day(Weekday::Sat, WorkDay::Off)
+                .with_day(Weekday::Sun, WorkDay::Off)
+                .with_day(Weekday::Mon, WorkDay::Off)
+                .with_day(Weekday::Tue, WorkDay::Off)
+                .with_day(Weekday::Wed, WorkDay::Off)
+                .with_day(Weekday::Thu, WorkDay::Off)
+                .with_day(Weekday::Fri, WorkDay::Off),
+            HolidayCalendar::iran(),
+        );
+        assert_eq!(
+            no_work.add_business_days(ymd(1403, 5, 10), 1),
+            Err(JalaliError::NoWorkingDays)
+        );
+    }
+}
@@ -0,0 +1,580 @@
+//! Validated Jalali calendar date.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, IsoWeek, NaiveDate, NaiveDateTime, TimeZone, Weekday};
+
+use crate::{
+    GREGORIAN_CE_JDN_OFFSET, JalaliError, MAX_JALALI_YEAR, MIN_JALALI_YEAR, MONTHS_PER_YEAR_COUNT,
+    compute_jdn_internal, is_leap, jalali_month_day_to_yday_internal, jalali_to_jdn_internal,
+    jalali_yday_to_month_day_internal, jdn_to_jalali_internal, parse_number, try_days_in_month,
+};
+
+/// What month and year arithmetic does when the target month is shorter than
+/// the starting day, e.g. 31 Shahrivar + 1 month or 30 Esfand + 1 year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonthEndPolicy {
+    /// Use the last day of the target month (31 Shahrivar + 1 month = 30 Mehr).
+    #[default]
+    Clamp,
+    /// Carry the extra days into the next month (31 Shahrivar + 1 month = 1 Aban).
+    Overflow,
+    /// Fail with `JalaliError::InvalidDay`.
+    Error,
+}
+
+/// A Jalali (Solar Hijri) date that is known to be valid.
+///
+/// Fields are ordered year, month, day so the derived `Ord` is chronological.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JalaliDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl JalaliDate {
+    /// Fails for a month outside 1-12, a day past the end of the month or a
+    /// year outside `MIN_JALALI_YEAR..=MAX_JALALI_YEAR`.
+    pub fn try_from_ymd(year: i32, month: u8, day: u8) -> Result<JalaliDate, JalaliError> {
+        if !(MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(&year) {
+            return Err(JalaliError::OutOfRange);
+        }
+        let days = try_days_in_month(year, month)?;
+        if day == 0 || day > days {
+            return Err(JalaliError::InvalidDay {
+                year,
+                month: month as u32,
+                day: day as u32,
+            });
+        }
+        Ok(JalaliDate { year, month, day })
+    }
+
+    pub fn from_ymd_opt(year: i32, month: u8, day: u8) -> Option<JalaliDate> {
+        JalaliDate::try_from_ymd(year, month, day).ok()
+    }
+
+    /// Builds a date from its 1-based day of year (1 Farvardin is 1).
+    pub fn try_from_ordinal(year: i32, ordinal: u16) -> Result<JalaliDate, JalaliError> {
+        if !(MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(&year) {
+            return Err(JalaliError::OutOfRange);
+        }
+        let days_in_year = if is_leap(year) { 366 } else { 365 };
+        if ordinal == 0 || ordinal > days_in_year {
+            return Err(JalaliError::InvalidOrdinal { year, ordinal });
+        }
+        let (month, day) = jalali_yday_to_month_day_internal(ordinal as i32 - 1);
+        Ok(JalaliDate { year, month, day })
+    }
+
+    pub fn from_ordinal_opt(year: i32, ordinal: u16) -> Option<JalaliDate> {
+        JalaliDate::try_from_ordinal(year, ordinal).ok()
+    }
+
+    /// Fails when the Gregorian date falls outside the supported Jalali year
+    /// range (e.g. before 622-03-22).
+    pub fn try_from_gregorian(date: NaiveDate) -> Result<JalaliDate, JalaliError> {
+        let jdn = compute_jdn_internal(date.year(), date.month(), date.day());
+        let (year, month, day) = jdn_to_jalali_internal(jdn);
+        if !(MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(&year) {
+            return Err(JalaliError::OutOfRange);
+        }
+        Ok(JalaliDate { year, month, day })
+    }
+
+    pub fn from_gregorian(date: NaiveDate) -> Option<JalaliDate> {
+        JalaliDate::try_from_gregorian(date).ok()
+    }
+
+    pub fn to_gregorian(self) -> NaiveDate {
+        let jdn = jalali_to_jdn_internal(self.year, self.month, self.day);
+        NaiveDate::from_num_days_from_ce_opt((jdn - GREGORIAN_CE_JDN_OFFSET) as i32)
+            .expect("every JalaliDate in the supported year range maps to a NaiveDate")
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Day of year, starting at 1 for 1 Farvardin.
+    pub fn ordinal(&self) -> u16 {
+        jalali_month_day_to_yday_internal(self.month, self.day) as u16 + 1
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.to_gregorian().weekday()
+    }
+
+    pub fn is_leap_year(&self) -> bool {
+        is_leap(self.year)
+    }
+
+    /// Julian Day Number; see the day-number functions such as
+    /// `jdn_to_mjd` for other day counts.
+    pub fn to_jdn(self) -> i64 {
+        jalali_to_jdn_internal(self.year, self.month, self.day)
+    }
+
+    /// The date of a Julian Day Number; `OutOfRange` before 1 Farvardin of
+    /// year 1 or after the last day of `MAX_JALALI_YEAR`.
+    pub fn try_from_jdn(jdn: i64) -> Result<JalaliDate, JalaliError> {
+        if jdn < jalali_to_jdn_internal(MIN_JALALI_YEAR, 1, 1)
+            || jdn >= jalali_to_jdn_internal(MAX_JALALI_YEAR + 1, 1, 1)
+        {
+            return Err(JalaliError::OutOfRange);
+        }
+        let (year, month, day) = jdn_to_jalali_internal(jdn);
+        Ok(JalaliDate { year, month, day })
+    }
+
+    fn from_jdn(jdn: i64) -> Result<JalaliDate, JalaliError> {
+        JalaliDate::try_from_jdn(jdn).map_err(|_| JalaliError::Overflow)
+    }
+
+    /// Moves by `days` (negative goes back).
+    pub fn checked_add_days(self, days: i64) -> Result<JalaliDate, JalaliError> {
+        let jdn = self
+            .to_jdn()
+            .checked_add(days)
+            .ok_or(JalaliError::Overflow)?;
+        JalaliDate::from_jdn(jdn)
+    }
+
+    /// Moves by whole Jalali months, keeping the day of month where `policy`
+    /// allows it.
+    pub fn checked_add_months(
+        self,
+        months: i64,
+        policy: MonthEndPolicy,
+    ) -> Result<JalaliDate, JalaliError> {
+        let months_per_year = MONTHS_PER_YEAR_COUNT as i64;
+        let total = (self.year as i64 * months_per_year + self.month as i64 - 1)
+            .checked_add(months)
+            .ok_or(JalaliError::Overflow)?;
+        let year = i32::try_from(total.div_euclid(months_per_year))
+            .ok()
+            .filter(|year| (MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(year))
+            .ok_or(JalaliError::Overflow)?;
+        let month = total.rem_euclid(months_per_year) as u8 + 1;
+
+        let days = try_days_in_month(year, month)?;
+        if self.day <= days {
+            return Ok(JalaliDate {
+                year,
+                month,
+                day: self.day,
+            });
+        }
+        let last = JalaliDate {
+            year,
+            month,
+            day: days,
+        };
+        match policy {
+            MonthEndPolicy::Clamp => Ok(last),
+            MonthEndPolicy::Overflow => last.checked_add_days((self.day - days) as i64),
+            MonthEndPolicy::Error => Err(JalaliError::InvalidDay {
+                year,
+                month: month as u32,
+                day: self.day as u32,
+            }),
+        }
+    }
+
+    /// Moves by whole Jalali years; only 30 Esfand can hit `policy`.
+    pub fn checked_add_years(
+        self,
+        years: i64,
+        policy: MonthEndPolicy,
+    ) -> Result<JalaliDate, JalaliError> {
+        let months = years
+            .checked_mul(MONTHS_PER_YEAR_COUNT as i64)
+            .ok_or(JalaliError::Overflow)?;
+        self.checked_add_months(months, policy)
+    }
+}
+
+impl From<JalaliDate> for NaiveDate {
+    fn from(date: JalaliDate) -> NaiveDate {
+        date.to_gregorian()
+    }
+}
+
+impl TryFrom<NaiveDate> for JalaliDate {
+    type Error = JalaliError;
+
+    fn try_from(date: NaiveDate) -> Result<JalaliDate, JalaliError> {
+        JalaliDate::try_from_gregorian(date)
+    }
+}
+
+// The date part; the time is dropped.
+impl TryFrom<NaiveDateTime> for JalaliDate {
+    type Error = JalaliError;
+
+    fn try_from(datetime: NaiveDateTime) -> Result<JalaliDate, JalaliError> {
+        JalaliDate::try_from_gregorian(datetime.date())
+    }
+}
+
+// The local date in the value's own time zone.
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for JalaliDate {
+    type Error = JalaliError;
+
+    fn try_from(datetime: DateTime<Tz>) -> Result<JalaliDate, JalaliError> {
+        JalaliDate::try_from_gregorian(datetime.date_naive())
+    }
+}
+
+/// Year, month, day and ordinal are the Jalali fields; `weekday`,
+/// `iso_week` and `num_days_from_ce` describe the same day as the
+/// Gregorian `NaiveDate` does. The `with_*` methods return `None` where
+/// the result is not a valid Jalali date, e.g. `with_day(31)` in Mehr.
+impl Datelike for JalaliDate {
+    fn year(&self) -> i32 {
+        self.year
+    }
+
+    fn month(&self) -> u32 {
+        self.month as u32
+    }
+
+    fn month0(&self) -> u32 {
+        self.month as u32 - 1
+    }
+
+    fn day(&self) -> u32 {
+        self.day as u32
+    }
+
+    fn day0(&self) -> u32 {
+        self.day as u32 - 1
+    }
+
+    fn ordinal(&self) -> u32 {
+        JalaliDate::ordinal(self) as u32
+    }
+
+    fn ordinal0(&self) -> u32 {
+        JalaliDate::ordinal(self) as u32 - 1
+    }
+
+    fn weekday(&self) -> Weekday {
+        JalaliDate::weekday(self)
+    }
+
+    fn iso_week(&self) -> IsoWeek {
+        self.to_gregorian().iso_week()
+    }
+
+    fn with_year(&self, year: i32) -> Option<JalaliDate> {
+        JalaliDate::from_ymd_opt(year, self.month, self.day)
+    }
+
+    fn with_month(&self, month: u32) -> Option<JalaliDate> {
+        JalaliDate::from_ymd_opt(self.year, u8::try_from(month).ok()?, self.day)
+    }
+
+    fn with_month0(&self, month0: u32) -> Option<JalaliDate> {
+        self.with_month(month0.checked_add(1)?)
+    }
+
+    fn with_day(&self, day: u32) -> Option<JalaliDate> {
+        JalaliDate::from_ymd_opt(self.year, self.month, u8::try_from(day).ok()?)
+    }
+
+    fn with_day0(&self, day0: u32) -> Option<JalaliDate> {
+        self.with_day(day0.checked_add(1)?)
+    }
+
+    fn with_ordinal(&self, ordinal: u32) -> Option<JalaliDate> {
+        JalaliDate::from_ordinal_opt(self.year, u16::try_from(ordinal).ok()?)
+    }
+
+    fn with_ordinal0(&self, ordinal0: u32) -> Option<JalaliDate> {
+        self.with_ordinal(ordinal0.checked_add(1)?)
+    }
+
+    // The default implementations assume Gregorian months and years.
+    fn num_days_from_ce(&self) -> i32 {
+        self.to_gregorian().num_days_from_ce()
+    }
+
+    fn num_days_in_month(&self) -> u8 {
+        try_days_in_month(self.year, self.month).expect("a valid date has a valid month")
+    }
+}
+
+// Same shape `sdate` accepts on the command line: YYYY/MM/DD.
+impl fmt::Display for JalaliDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}/{:02}/{:02}", self.year, self.month, self.day)
+    }
+}
+
+// Accepts YYYY/MM/DD or YYYY-MM-DD, in Latin, Persian or Arabic-Indic digits.
+impl FromStr for JalaliDate {
+    type Err = JalaliError;
+
+    fn from_str(s: &str) -> Result<JalaliDate, JalaliError> {
+        let err = || JalaliError::InvalidFormat {
+            input: s.to_string(),
+        };
+
+        let parts: Vec<&str> = s.trim().split(['/', '-']).collect();
+        if parts.len() != 3 {
+            return Err(err());
+        }
+        let year = parse_number::<i32>(parts[0]).map_err(|_| err())?;
+        let month = parse_number::<u8>(parts[1]).map_err(|_| err())?;
+        let day = parse_number::<u8>(parts[2]).map_err(|_| err())?;
+
+        JalaliDate::try_from_ymd(year, month, day)
+    }
+}
+
+/// Shorthand for a date the test knows to be valid.
+#[cfg(test)]
+pub(crate) fn ymd(year: i32, month: u8, day: u8) -> JalaliDate {
+    JalaliDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ymd_opt_validation() {
+        assert!(JalaliDate::from_ymd_opt(1399, 12, 30).is_some());
+        assert!(JalaliDate::from_ymd_opt(1400, 12, 30).is_none());
+        assert!(JalaliDate::from_ymd_opt(1400, 0, 1).is_none());
+        assert!(JalaliDate::from_ymd_opt(1400, 13, 1).is_none());
+        assert!(JalaliDate::from_ymd_opt(1400, 7, 31).is_none());
+        assert!(JalaliDate::from_ymd_opt(1400, 1, 0).is_none());
+        assert!(JalaliDate::from_ymd_opt(0, 1, 1).is_none());
+        assert!(JalaliDate::from_ymd_opt(MAX_JALALI_YEAR + 1, 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_try_from_ymd_errors() {
+        assert_eq!(
+            JalaliDate::try_from_ymd(1400, 13, 1),
+            Err(JalaliError::InvalidMonth { month: 13 })
+        );
+        assert_eq!(
+            JalaliDate::try_from_ymd(1400, 12, 30),
+            Err(JalaliError::InvalidDay {
+                year: 1400,
+                month: 12,
+                day: 30
+            })
+        );
+        assert_eq!(
+            JalaliDate::try_from_ymd(0, 1, 1),
+            Err(JalaliError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_ordinal_round_trip() {
+        let date = JalaliDate::from_ymd_opt(1403, 7, 1).unwrap();
+        assert_eq!(date.ordinal(), 187);
+        assert_eq!(JalaliDate::from_ordinal_opt(1403, 187), Some(date));
+
+        assert_eq!(
+            JalaliDate::from_ordinal_opt(1399, 366),
+            JalaliDate::from_ymd_opt(1399, 12, 30)
+        );
+        assert!(JalaliDate::from_ordinal_opt(1400, 366).is_none());
+        assert!(JalaliDate::from_ordinal_opt(1400, 0).is_none());
+    }
+
+    #[test]
+    fn test_gregorian_conversion() {
+        let jalali = JalaliDate::from_ymd_opt(1402, 9, 30).unwrap();
+        let gregorian = NaiveDate::from_ymd_opt(2023, 12, 21).unwrap();
+        assert_eq!(jalali.to_gregorian(), gregorian);
+        assert_eq!(NaiveDate::from(jalali), gregorian);
+        assert_eq!(JalaliDate::from_gregorian(gregorian), Some(jalali));
+
+        let before_epoch = NaiveDate::from_ymd_opt(622, 3, 21).unwrap();
+        assert!(JalaliDate::from_gregorian(before_epoch).is_none());
+        assert_eq!(
+            JalaliDate::try_from(before_epoch),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(
+            JalaliDate::from_gregorian(before_epoch.succ_opt().unwrap()),
+            JalaliDate::from_ymd_opt(1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_weekday() {
+        // 1 Farvardin 1399 was a Friday.
+        let date = JalaliDate::from_ymd_opt(1399, 1, 1).unwrap();
+        assert_eq!(date.weekday(), Weekday::Fri);
+    }
+
+    #[test]
+    fn test_datelike() {
+        fn describe<D: Datelike>(date: &D) -> (i32, u32, u32, u32, u32) {
+            (
+                date.year(),
+                date.month(),
+                date.day(),
+                date.ordinal(),
+                date.quarter(),
+            )
+        }
+
+        let date = JalaliDate::from_ymd_opt(1403, 7, 30).unwrap();
+        assert_eq!(describe(&date), (1403, 7, 30, 216, 3));
+        assert_eq!(Datelike::num_days_in_month(&date), 30);
+        assert_eq!(
+            Datelike::num_days_from_ce(&date),
+            date.to_gregorian().num_days_from_ce()
+        );
+        assert_eq!(Datelike::weekday(&date), Weekday::Mon);
+        assert_eq!(date.with_month(6), JalaliDate::from_ymd_opt(1403, 6, 30));
+        assert_eq!(date.with_day(31), None);
+        assert_eq!(date.with_month0(12), None);
+        assert_eq!(date.with_year(1404), JalaliDate::from_ymd_opt(1404, 7, 30));
+        assert_eq!(
+            JalaliDate::from_ymd_opt(1403, 12, 30)
+                .unwrap()
+                .with_year(1404),
+            None
+        );
+        assert_eq!(date.with_ordinal0(0), JalaliDate::from_ymd_opt(1403, 1, 1));
+    }
+
+    #[test]
+    fn test_chrono_conversions() {
+        let datetime = NaiveDate::from_ymd_opt(2024, 3, 19)
+            .unwrap()
+            .and_hms_opt(22, 0, 0)
+            .unwrap();
+        let expected = JalaliDate::from_ymd_opt(1402, 12, 29).unwrap();
+        assert_eq!(JalaliDate::try_from(datetime), Ok(expected));
+        assert_eq!(JalaliDate::try_from(datetime.and_utc()), Ok(expected));
+
+        // Already 1 Farvardin in Tehran.
+        let tehran = datetime
+            .and_utc()
+            .with_timezone(&chrono::FixedOffset::east_opt(12_600).unwrap());
+        assert_eq!(
+            JalaliDate::try_from(tehran),
+            JalaliDate::try_from_ymd(1403, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let a = JalaliDate::from_ymd_opt(1402, 12, 29).unwrap();
+        let b = JalaliDate::from_ymd_opt(1403, 1, 1).unwrap();
+        let c = JalaliDate::from_ymd_opt(1403, 2, 1).unwrap();
+        assert!(a < b && b < c);
+    }
+
+    #[test]
+    fn test_add_days() {
+        let date = JalaliDate::from_ymd_opt(1403, 12, 30).unwrap();
+        assert_eq!(
+            date.checked_add_days(1),
+            Ok(JalaliDate::from_ymd_opt(1404, 1, 1).unwrap())
+        );
+        assert_eq!(
+            date.checked_add_days(-365),
+            Ok(JalaliDate::from_ymd_opt(1403, 1, 1).unwrap())
+        );
+        let first = JalaliDate::from_ymd_opt(1, 1, 1).unwrap();
+        assert_eq!(first.checked_add_days(-1), Err(JalaliError::Overflow));
+        assert_eq!(first.checked_add_days(i64::MAX), Err(JalaliError::Overflow));
+    }
+
+    #[test]
+    fn test_add_months_and_years() {
+        assert_eq!(
+            ymd(1403, 1, 30).checked_add_months(1, MonthEndPolicy::Error),
+            Ok(ymd(1403, 2, 30))
+        );
+        assert_eq!(
+            ymd(1403, 1, 15).checked_add_months(-2, MonthEndPolicy::Error),
+            Ok(ymd(1402, 11, 15))
+        );
+        assert_eq!(
+            ymd(1403, 6, 31).checked_add_months(1, MonthEndPolicy::Clamp),
+            Ok(ymd(1403, 7, 30))
+        );
+        assert_eq!(
+            ymd(1403, 6, 31).checked_add_months(1, MonthEndPolicy::Overflow),
+            Ok(ymd(1403, 8, 1))
+        );
+        assert_eq!(
+            ymd(1403, 6, 31).checked_add_months(1, MonthEndPolicy::Error),
+            Err(JalaliError::InvalidDay {
+                year: 1403,
+                month: 7,
+                day: 31
+            })
+        );
+        assert_eq!(
+            ymd(1402, 11, 30).checked_add_months(1, MonthEndPolicy::Clamp),
+            Ok(ymd(1402, 12, 29))
+        );
+
+        assert_eq!(
+            ymd(1403, 12, 30).checked_add_years(1, MonthEndPolicy::Clamp),
+            Ok(ymd(1404, 12, 29))
+        );
+        assert_eq!(
+            ymd(1403, 12, 30).checked_add_years(1, MonthEndPolicy::Overflow),
+            Ok(ymd(1405, 1, 1))
+        );
+        assert_eq!(
+            ymd(1399, 12, 30).checked_add_years(4, MonthEndPolicy::Error),
+            Ok(ymd(1403, 12, 30))
+        );
+        assert_eq!(
+            ymd(1, 5, 1).checked_add_years(-1, MonthEndPolicy::Clamp),
+            Err(JalaliError::Overflow)
+        );
+        assert_eq!(
+            ymd(1403, 1, 1).checked_add_years(i64::MAX, MonthEndPolicy::Clamp),
+            Err(JalaliError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_display_and_from_str() {
+        let date = JalaliDate::from_ymd_opt(1379, 8, 15).unwrap();
+        assert_eq!(date.to_string(), "1379/08/15");
+        assert_eq!("1379/08/15".parse::<JalaliDate>(), Ok(date));
+        assert_eq!("1379-8-15".parse::<JalaliDate>(), Ok(date));
+        assert_eq!(
+            "1400/12/30".parse::<JalaliDate>(),
+            Err(JalaliError::InvalidDay {
+                year: 1400,
+                month: 12,
+                day: 30
+            })
+        );
+        assert!(matches!(
+            "1400/12".parse::<JalaliDate>(),
+            Err(JalaliError::InvalidFormat { .. })
+        ));
+        assert_eq!("۱۳۷۹/۰۸/١٥".parse::<JalaliDate>(), Ok(date));
+        assert!("abc/01/01".parse::<JalaliDate>().is_err());
+    }
+}
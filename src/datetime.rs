@@ -0,0 +1,332 @@
+//! Jalali date and time in a time zone.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use chrono::{
+    DateTime, FixedOffset, LocalResult, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone,
+};
+
+use crate::{JalaliDate, JalaliError, JalaliFormat};
+
+/// How a local wall-clock time is resolved when a clock change (e.g. the
+/// start or end of daylight saving time) skips or repeats it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DstPolicy {
+    /// Earlier instant for a repeated time; a skipped time is moved forward
+    /// by the length of the gap (02:30 in a 02:00 -> 03:00 gap is 03:30).
+    #[default]
+    Compatible,
+    /// Earlier instant for a repeated time; a skipped time is moved back by
+    /// the length of the gap (02:30 becomes 01:30).
+    Earlier,
+    /// Later instant for a repeated time; a skipped time is moved forward.
+    Later,
+    /// Fail with `JalaliError::AmbiguousLocalTime` or
+    /// `JalaliError::NonexistentLocalTime`.
+    Error,
+}
+
+/// An instant seen in time zone `Tz`, with the Jalali date of its local
+/// wall-clock time.
+///
+/// Comparison and hashing use the instant, as for `chrono::DateTime`.
+#[derive(Debug, Clone)]
+pub struct JalaliDateTime<Tz: TimeZone> {
+    date: JalaliDate,
+    datetime: DateTime<Tz>,
+}
+
+impl<Tz: TimeZone> JalaliDateTime<Tz> {
+    /// Fails when the local date is outside the supported Jalali range.
+    pub fn from_datetime(datetime: DateTime<Tz>) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        let date = JalaliDate::try_from_gregorian(datetime.date_naive())?;
+        Ok(JalaliDateTime { date, datetime })
+    }
+
+    /// The instant `secs` seconds and `nanos` nanoseconds after the Unix
+    /// epoch, in `tz`.
+    pub fn from_timestamp(
+        secs: i64,
+        nanos: u32,
+        tz: &Tz,
+    ) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        let utc = DateTime::from_timestamp(secs, nanos).ok_or(JalaliError::OutOfRange)?;
+        JalaliDateTime::from_datetime(utc.with_timezone(tz))
+    }
+
+    pub fn from_timestamp_nanos(nanos: i64, tz: &Tz) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        JalaliDateTime::from_datetime(DateTime::from_timestamp_nanos(nanos).with_timezone(tz))
+    }
+
+    /// Wall-clock `date` and `time` in `tz`; `policy` decides what happens
+    /// when a clock change skips or repeats that time.
+    pub fn from_local(
+        date: JalaliDate,
+        time: NaiveTime,
+        tz: &Tz,
+        policy: DstPolicy,
+    ) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        let local = date.to_gregorian().and_time(time);
+        let datetime = match tz.from_local_datetime(&local) {
+            LocalResult::Single(datetime) => datetime,
+            LocalResult::Ambiguous(earlier, later) => match policy {
+                DstPolicy::Compatible | DstPolicy::Earlier => earlier,
+                DstPolicy::Later => later,
+                DstPolicy::Error => return Err(JalaliError::AmbiguousLocalTime { date, time }),
+            },
+            LocalResult::None => {
+                // Reading the local time with the offset in force before the
+                // gap lands after it, and vice versa.
+                let offset = match policy {
+                    DstPolicy::Compatible | DstPolicy::Later => {
+                        offset_near(tz, local, -TimeDelta::days(1))
+                    }
+                    DstPolicy::Earlier => offset_near(tz, local, TimeDelta::days(1)),
+                    DstPolicy::Error => {
+                        return Err(JalaliError::NonexistentLocalTime { date, time });
+                    }
+                };
+                let utc = local - TimeDelta::seconds(offset.local_minus_utc() as i64);
+                tz.from_utc_datetime(&utc)
+            }
+        };
+        JalaliDateTime::from_datetime(datetime)
+    }
+
+    pub fn date(&self) -> JalaliDate {
+        self.date
+    }
+
+    /// Local wall-clock time.
+    pub fn time(&self) -> NaiveTime {
+        self.datetime.time()
+    }
+
+    pub fn offset(&self) -> &Tz::Offset {
+        self.datetime.offset()
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.datetime.timezone()
+    }
+
+    pub fn as_datetime(&self) -> &DateTime<Tz> {
+        &self.datetime
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.datetime.timestamp()
+    }
+
+    /// Nanoseconds past the whole second of `timestamp`.
+    pub fn timestamp_subsec_nanos(&self) -> u32 {
+        self.datetime.timestamp_subsec_nanos()
+    }
+
+    /// Nanoseconds since the Unix epoch, or `None` outside the years
+    /// 1677-2262 (Gregorian) that fit in an `i64`.
+    pub fn timestamp_nanos_opt(&self) -> Option<i64> {
+        self.datetime.timestamp_nanos_opt()
+    }
+
+    /// The same instant in another time zone.
+    pub fn with_timezone<Tz2: TimeZone>(
+        &self,
+        tz: &Tz2,
+    ) -> Result<JalaliDateTime<Tz2>, JalaliError> {
+        JalaliDateTime::from_datetime(self.datetime.with_timezone(tz))
+    }
+
+    /// Moves to `date`, keeping the wall-clock time.
+    pub fn with_date(
+        &self,
+        date: JalaliDate,
+        policy: DstPolicy,
+    ) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        JalaliDateTime::from_local(date, self.time(), &self.timezone(), policy)
+    }
+
+    /// Moves to wall-clock `time` on the same date.
+    pub fn with_time(
+        &self,
+        time: NaiveTime,
+        policy: DstPolicy,
+    ) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        JalaliDateTime::from_local(self.date, time, &self.timezone(), policy)
+    }
+
+    /// Adds elapsed time, so one hour after 01:30 on the night clocks go
+    /// forward at 02:00 is 03:30.
+    pub fn checked_add_signed(&self, delta: TimeDelta) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        let datetime = self
+            .datetime
+            .clone()
+            .checked_add_signed(delta)
+            .ok_or(JalaliError::Overflow)?;
+        JalaliDateTime::from_datetime(datetime).map_err(|_| JalaliError::Overflow)
+    }
+
+    /// Formats the local date and time; `%Z` is the offset's own name (e.g.
+    /// `+0330` or `EST`).
+    pub fn format<'a>(&self, pattern: &'a str) -> JalaliFormat<'a>
+    where
+        Tz::Offset: fmt::Display,
+    {
+        self.date
+            .format(pattern)
+            .with_time(self.time())
+            .with_zone(self.offset().fix(), self.offset().to_string())
+    }
+}
+
+// Offset in force a day away from `local`; transitions are months apart.
+fn offset_near<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime, shift: TimeDelta) -> FixedOffset {
+    tz.offset_from_utc_datetime(&(local + shift)).fix()
+}
+
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for JalaliDateTime<Tz> {
+    type Error = JalaliError;
+
+    fn try_from(datetime: DateTime<Tz>) -> Result<JalaliDateTime<Tz>, JalaliError> {
+        JalaliDateTime::from_datetime(datetime)
+    }
+}
+
+impl<Tz: TimeZone> From<JalaliDateTime<Tz>> for DateTime<Tz> {
+    fn from(datetime: JalaliDateTime<Tz>) -> DateTime<Tz> {
+        datetime.datetime
+    }
+}
+
+impl<Tz: TimeZone> From<JalaliDateTime<Tz>> for JalaliDate {
+    fn from(datetime: JalaliDateTime<Tz>) -> JalaliDate {
+        datetime.date
+    }
+}
+
+impl<Tz: TimeZone> Copy for JalaliDateTime<Tz> where Tz::Offset: Copy {}
+
+impl<Tz: TimeZone, Tz2: TimeZone> PartialEq<JalaliDateTime<Tz2>> for JalaliDateTime<Tz> {
+    fn eq(&self, other: &JalaliDateTime<Tz2>) -> bool {
+        self.datetime == other.datetime
+    }
+}
+
+impl<Tz: TimeZone> Eq for JalaliDateTime<Tz> {}
+
+impl<Tz: TimeZone> PartialOrd for JalaliDateTime<Tz> {
+    fn partial_cmp(&self, other: &JalaliDateTime<Tz>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Tz: TimeZone> Ord for JalaliDateTime<Tz> {
+    fn cmp(&self, other: &JalaliDateTime<Tz>) -> Ordering {
+        self.datetime.cmp(&other.datetime)
+    }
+}
+
+impl<Tz: TimeZone> Hash for JalaliDateTime<Tz> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.datetime.hash(state);
+    }
+}
+
+// e.g. "1403/05/07 09:05:30 +03:30"
+impl<Tz: TimeZone> fmt::Display for JalaliDateTime<Tz>
+where
+    Tz::Offset: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.date, self.time(), self.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+    use chrono::Utc;
+    use chrono_tz::{America::New_York, Asia::Tehran};
+
+    fn hm(hour: u32, min: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let datetime = JalaliDateTime::from_timestamp(1_700_000_000, 5, &Tehran).unwrap();
+        assert_eq!(datetime.date(), ymd(1402, 8, 24));
+        assert_eq!(datetime.to_string(), "1402/08/24 01:43:20.000000005 +0330");
+        assert_eq!(datetime.timestamp(), 1_700_000_000);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 5);
+        assert_eq!(
+            datetime.timestamp_nanos_opt(),
+            Some(1_700_000_000_000_000_005)
+        );
+
+        // The same instant is still the previous day in UTC.
+        let utc = datetime.with_timezone(&Utc).unwrap();
+        assert_eq!(utc.date(), ymd(1402, 8, 23));
+        assert_eq!(utc, datetime);
+        assert_eq!(
+            JalaliDateTime::from_timestamp_nanos(1_700_000_000_000_000_005, &Tehran).unwrap(),
+            datetime
+        );
+        assert_eq!(
+            datetime.format("%F %T %Z").to_string(),
+            "1402-08-24 01:43:20 +0330"
+        );
+    }
+
+    #[test]
+    fn test_from_local_in_dst_gap() {
+        // Clocks jumped from 02:00 to 03:00 on 20 Esfand 1402 (2024-03-10).
+        let date = ymd(1402, 12, 20);
+        let later =
+            JalaliDateTime::from_local(date, hm(2, 30), &New_York, DstPolicy::Compatible).unwrap();
+        assert_eq!(later.time(), hm(3, 30));
+        let earlier =
+            JalaliDateTime::from_local(date, hm(2, 30), &New_York, DstPolicy::Earlier).unwrap();
+        assert_eq!(earlier.time(), hm(1, 30));
+        assert_eq!(
+            JalaliDateTime::from_local(date, hm(2, 30), &New_York, DstPolicy::Error),
+            Err(JalaliError::NonexistentLocalTime {
+                date,
+                time: hm(2, 30)
+            })
+        );
+
+        // Elapsed time skips the gap too.
+        let before = JalaliDateTime::from_local(date, hm(1, 30), &New_York, DstPolicy::Error)
+            .unwrap()
+            .checked_add_signed(TimeDelta::hours(1))
+            .unwrap();
+        assert_eq!(before.time(), hm(3, 30));
+    }
+
+    #[test]
+    fn test_from_local_in_dst_overlap() {
+        // Clocks went back from 02:00 to 01:00 on 13 Aban 1403 (2024-11-03).
+        let date = ymd(1403, 8, 13);
+        let earlier =
+            JalaliDateTime::from_local(date, hm(1, 30), &New_York, DstPolicy::Compatible).unwrap();
+        let later =
+            JalaliDateTime::from_local(date, hm(1, 30), &New_York, DstPolicy::Later).unwrap();
+        assert_eq!(later.timestamp() - earlier.timestamp(), 3600);
+        assert_eq!(earlier.time(), later.time());
+        assert!(matches!(
+            JalaliDateTime::from_local(date, hm(1, 30), &New_York, DstPolicy::Error),
+            Err(JalaliError::AmbiguousLocalTime { .. })
+        ));
+
+        let next_day = earlier
+            .with_date(ymd(1403, 8, 14), DstPolicy::Error)
+            .unwrap();
+        assert_eq!(next_day.time(), hm(1, 30));
+        assert_eq!(next_day.timestamp() - earlier.timestamp(), 25 * 3600);
+    }
+}
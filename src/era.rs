@@ -0,0 +1,158 @@
+//! Year numbering eras of the solar calendar.
+//!
+//! The months and days are those of the Jalali calendar in every era; only
+//! the year number differs by a fixed offset from the Solar Hijri year.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{JalaliDate, JalaliError, Locale};
+
+const ERA_NAMES: [&str; 3] = ["Solar Hijri", "Imperial", "Yazdgerdi"];
+const PERSIAN_ERA_NAMES: [&str; 3] = ["هجری شمسی", "شاهنشاهی", "یزدگردی"];
+
+/// How years are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Era {
+    /// Years since the Hijra of 622, the official Iranian count.
+    #[default]
+    SolarHijri,
+    /// The imperial (Shahanshahi) count from the accession of Cyrus, used
+    /// in Iran in 1976-1978 and shown by `scal -P`; 1180 years ahead of
+    /// Solar Hijri.
+    Imperial,
+    /// Years since the accession of Yazdegerd III in 632, as in the
+    /// Zoroastrian calendars; 10 years behind Solar Hijri.
+    Yazdgerdi,
+}
+
+impl Era {
+    pub const ALL: [Era; 3] = [Era::SolarHijri, Era::Imperial, Era::Yazdgerdi];
+
+    /// Years added to a Solar Hijri year to get the year of this era.
+    pub fn offset(self) -> i32 {
+        match self {
+            Era::SolarHijri => 0,
+            Era::Imperial => 1180,
+            Era::Yazdgerdi => -10,
+        }
+    }
+
+    /// Converts a Solar Hijri year to this era. Conversions saturate at
+    /// the ends of `i32`, leaving a year no date can have.
+    pub fn year_from_solar_hijri(self, year: i32) -> i32 {
+        year.saturating_add(self.offset())
+    }
+
+    pub fn year_to_solar_hijri(self, year: i32) -> i32 {
+        year.saturating_sub(self.offset())
+    }
+
+    /// Converts a year of this era to `to`, e.g.
+    /// `Era::Imperial.convert_year(2537, Era::SolarHijri) == 1357`.
+    pub fn convert_year(self, year: i32, to: Era) -> i32 {
+        to.year_from_solar_hijri(self.year_to_solar_hijri(year))
+    }
+
+    /// Name of the era; in Persian for locales written in Arabic script.
+    pub fn name(self, locale: Locale) -> &'static str {
+        let names = if locale.is_arabic_script() {
+            &PERSIAN_ERA_NAMES
+        } else {
+            &ERA_NAMES
+        };
+        names[self as usize]
+    }
+
+    pub(crate) fn names() -> [&'static [&'static str]; 2] {
+        [&ERA_NAMES, &PERSIAN_ERA_NAMES]
+    }
+}
+
+impl JalaliDate {
+    /// The year of this date counted in `era`.
+    pub fn era_year(&self, era: Era) -> i32 {
+        era.year_from_solar_hijri(self.year())
+    }
+}
+
+impl fmt::Display for Era {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Era::SolarHijri => "solar-hijri",
+            Era::Imperial => "imperial",
+            Era::Yazdgerdi => "yazdgerdi",
+        })
+    }
+}
+
+impl FromStr for Era {
+    type Err = JalaliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "solar-hijri" | "sh" | "hijri-shamsi" => Ok(Era::SolarHijri),
+            "imperial" | "pahlavi" | "shahanshahi" => Ok(Era::Imperial),
+            "yazdgerdi" | "yz" => Ok(Era::Yazdgerdi),
+            _ => Err(JalaliError::InvalidEra {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_era_years() {
+        assert_eq!(Era::Imperial.convert_year(2537, Era::SolarHijri), 1357);
+        assert_eq!(Era::SolarHijri.convert_year(1403, Era::Yazdgerdi), 1393);
+        assert_eq!(Era::Yazdgerdi.convert_year(1393, Era::Imperial), 2583);
+        assert_eq!(ymd(1403, 5, 12).era_year(Era::Imperial), 2583);
+        assert_eq!(Era::Imperial.year_to_solar_hijri(i32::MIN), i32::MIN);
+
+        for era in Era::ALL {
+            assert_eq!(era.to_string().parse::<Era>(), Ok(era));
+        }
+        assert_eq!("Pahlavi".parse::<Era>(), Ok(Era::Imperial));
+        assert_eq!(
+            "julian".parse::<Era>(),
+            Err(JalaliError::InvalidEra {
+                name: "julian".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_era_formatting_and_parsing() {
+        let date = ymd(1403, 5, 12);
+        let format = |pattern| date.format(pattern).era(Era::Imperial).to_string();
+        assert_eq!(format("%Y/%m/%d"), "2583/05/12");
+        assert_eq!(format("%-d %B %EY"), "12 Mordad 2583 Imperial");
+        assert_eq!(
+            date.format("%EY")
+                .era(Era::Yazdgerdi)
+                .locale(Locale::Persian)
+                .to_string(),
+            "۱۳۹۳ یزدگردی"
+        );
+        assert_eq!(date.format("%EC").to_string(), "Solar Hijri");
+
+        assert_eq!(
+            JalaliDate::parse_from_str("12 Mordad 2583 Imperial", "%d %B %EY"),
+            Ok(date)
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("۱۲ مرداد ۱۳۹۳ یزدگردی", "%d %B %Y %EC"),
+            Ok(date)
+        );
+        assert_eq!(
+            crate::JalaliParsed::parse_with_era("2583/05/12", "%Y/%m/%d", Era::Imperial)
+                .map(|parsed| parsed.date()),
+            Ok(date)
+        );
+    }
+}
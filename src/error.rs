@@ -0,0 +1,109 @@
+//! Error type returned by the non-panicking (`try_*`) conversions.
+
+use std::fmt;
+
+use chrono::NaiveTime;
+
+use crate::{JalaliDate, MAX_JALALI_YEAR, MIN_JALALI_YEAR, MIN_PROLEPTIC_JALALI_YEAR};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JalaliError {
+    /// Month outside 1-12 (Jalali or Gregorian, depending on the input).
+    InvalidMonth { month: u32 },
+    /// Day is zero or past the end of the given month.
+    InvalidDay { year: i32, month: u32, day: u32 },
+    /// Day of year is zero or past the end of the year.
+    InvalidOrdinal { year: i32, ordinal: u16 },
+    /// Week number is zero or past the last week of the week-based year.
+    InvalidWeek { year: i32, week: u32 },
+    /// The date is valid on its own but outside the range this library converts.
+    OutOfRange,
+    /// Date arithmetic produced a value that does not fit the calendar range.
+    Overflow,
+    /// Text could not be read as a YYYY/MM/DD date.
+    InvalidFormat { input: String },
+    /// Text did not match a parse pattern; `position` is the 0-based
+    /// character offset where matching failed.
+    Parse { position: usize, expected: String },
+    /// A line of holiday data could not be read; `line` is 1-based.
+    HolidayData { line: usize, reason: String },
+    /// A line of a Hijri month table could not be read; `line` is 1-based.
+    HijriTable { line: usize, reason: String },
+    /// A locale name that is not one of `Locale`'s tags.
+    InvalidLocale { tag: String },
+    /// An era name that is not one of `Era`'s.
+    InvalidEra { name: String },
+    /// Looking for a business day under a workweek that has none.
+    NoWorkingDays,
+    /// The local time is skipped by a clock change in the time zone.
+    NonexistentLocalTime { date: JalaliDate, time: NaiveTime },
+    /// The local time is repeated by a clock change in the time zone.
+    AmbiguousLocalTime { date: JalaliDate, time: NaiveTime },
+}
+
+impl fmt::Display for JalaliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JalaliError::InvalidMonth { month } => {
+                write!(f, "Invalid month {}. Month should be 1-12", month)
+            }
+            JalaliError::InvalidDay { year, month, day } => write!(
+                f,
+                "Invalid day {} for month {} in year {}",
+                day, month, year
+            ),
+            JalaliError::InvalidOrdinal { year, ordinal } => {
+                write!(f, "Invalid day of year {} for year {}", ordinal, year)
+            }
+            JalaliError::InvalidWeek { year, week } => {
+                write!(f, "Invalid week {} for week-based year {}", week, year)
+            }
+            JalaliError::OutOfRange => write!(
+                f,
+                "Date is out of the supported range (Jalali years {}-{}, Gregorian 622-03-22 onwards, or from about year {} with a proleptic rule)",
+                MIN_JALALI_YEAR, MAX_JALALI_YEAR, MIN_PROLEPTIC_JALALI_YEAR
+            ),
+            JalaliError::Overflow => write!(f, "Date arithmetic overflowed the supported range"),
+            JalaliError::InvalidFormat { input } => write!(
+                f,
+                "Invalid date format '{}'. Use YYYY/MM/DD. Example: 1379/08/15",
+                input
+            ),
+            JalaliError::Parse { position, expected } => write!(
+                f,
+                "Invalid input at character {}: expected {}",
+                position + 1,
+                expected
+            ),
+            JalaliError::HolidayData { line, reason } => {
+                write!(f, "Invalid holiday data on line {}: {}", line, reason)
+            }
+            JalaliError::HijriTable { line, reason } => {
+                write!(f, "Invalid Hijri month table on line {}: {}", line, reason)
+            }
+            JalaliError::InvalidLocale { tag } => write!(
+                f,
+                "Unknown locale '{}'. Use one of latin, fa, prs, prs-latn, ps or ckb",
+                tag
+            ),
+            JalaliError::InvalidEra { name } => write!(
+                f,
+                "Unknown era '{}'. Use one of solar-hijri, imperial or yazdgerdi",
+                name
+            ),
+            JalaliError::NoWorkingDays => write!(f, "The workweek has no working days"),
+            JalaliError::NonexistentLocalTime { date, time } => write!(
+                f,
+                "Local time {} {} does not exist in this time zone (skipped by a clock change)",
+                date, time
+            ),
+            JalaliError::AmbiguousLocalTime { date, time } => write!(
+                f,
+                "Local time {} {} is ambiguous in this time zone (repeated by a clock change)",
+                date, time
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JalaliError {}
@@ -0,0 +1,503 @@
+//! strftime-style formatting of Jalali dates.
+//!
+//! Specifiers follow GNU `date`, with the date fields read on the Jalali
+//! calendar:
+//!
+//! | Spec | Meaning                                        | Example       |
+//! |------|------------------------------------------------|---------------|
+//! | `%Y` | year, at least 4 digits                        | `1403`        |
+//! | `%C` | century (year / 100)                           | `14`          |
+//! | `%y` | year modulo 100                                | `03`          |
+//! | `%EC`| era name                                       | `Solar Hijri` |
+//! | `%EY`| year with era name, `%Y %EC`                   | `1403 Solar Hijri` |
+//! | `%~Y`| year in words                                  | `one thousand four hundred and three` |
+//! | `%~d`| day of month as an ordinal word                | `seventh`     |
+//! | `%~D`| date written out, `%~d %B %~Y` in Persian      | `seventh of Mordad, one thousand four hundred and three` |
+//! | `%m` | month number                                   | `05`          |
+//! | `%B` | month name                                     | `Mordad`      |
+//! | `%b` | abbreviated month name (also `%h`)             | `Mor`         |
+//! | `%d` | day of month, zero padded                      | `07`          |
+//! | `%e` | day of month, space padded                     | ` 7`          |
+//! | `%j` | day of year                                    | `131`         |
+//! | `%A` | weekday name                                   | `Jomeh`       |
+//! | `%a` | abbreviated weekday name                       | `Jom`         |
+//! | `%u` | weekday, Saturday = 1 ... Friday = 7           | `7`           |
+//! | `%w` | weekday, Saturday = 0 ... Friday = 6           | `6`           |
+//! | `%H` | hour (00-23), `%k` space padded                | `09`          |
+//! | `%I` | hour (01-12), `%l` space padded                | `09`          |
+//! | `%p` | AM/PM (`%P` lower case)                        | `AM`          |
+//! | `%M` | minute                                         | `05`          |
+//! | `%S` | second                                         | `30`          |
+//! | `%N` | nanoseconds                                    | `000000000`   |
+//! | `%s` | seconds since the Unix epoch                   | `1722665130`  |
+//! | `%z` | UTC offset `+hhmm` (`%:z` gives `+hh:mm`)      | `+0330`       |
+//! | `%Z` | zone name                                      | `+0330`       |
+//! | `%D` | `%Y/%m/%d`                                     | `1403/05/07`  |
+//! | `%F` | `%Y-%m-%d`                                     | `1403-05-07`  |
+//! | `%T` | `%H:%M:%S`, `%R` is `%H:%M`                    | `09:05:30`    |
+//! | `%c` | `%a %B %d %T %Z %-Y`, the `sdate` default      |               |
+//! | `%n` `%t` `%%` | newline, tab, percent                |               |
+//!
+//! After `%` an optional flag (`-` no padding, `_` pad with spaces, `0` pad
+//! with zeros, `^` upper case, `~` in words) and a field width may follow,
+//! e.g. `%-d` or `%10B`. The `~` flag is our own; it is not `%O`, which
+//! POSIX reserves for alternative digits, and the parser does not read it
+//! back. Unknown specifiers are copied through unchanged. The year fields count in the era set with `era`
+//! (Solar Hijri by default).
+//!
+//! Persian has no customary abbreviations of month and weekday names, so
+//! `%b` and `%a` give the full names in locales written in Arabic script.
+//! The `~` words are Persian for Iranian Persian and Dari in Arabic script
+//! and English for every other locale, Pashto and Kurdish included.
+
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveTime, TimeZone, Timelike};
+
+use crate::{
+    Era, JalaliDate, JalaliDateTime, JalaliError, Locale, UNIX_EPOCH_JDN, english_cardinal,
+    english_ordinal, persian_cardinal, persian_ordinal, to_persian_numerals,
+};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A Jalali date (and optionally a time and zone) paired with a format
+/// pattern. Nothing is rendered until it is displayed.
+///
+/// Without `with_time` the time is midnight; without `with_zone` the zone
+/// is UTC.
+#[derive(Debug, Clone)]
+pub struct JalaliFormat<'a> {
+    date: JalaliDate,
+    time: NaiveTime,
+    offset: FixedOffset,
+    zone_name: String,
+    pattern: &'a str,
+    persian_digits: bool,
+    locale: Locale,
+    era: Era,
+}
+
+impl JalaliDate {
+    pub fn format<'a>(&self, pattern: &'a str) -> JalaliFormat<'a> {
+        JalaliFormat {
+            date: *self,
+            time: NaiveTime::MIN,
+            offset: FixedOffset::east_opt(0).expect("zero offset is valid"),
+            zone_name: "UTC".to_string(),
+            pattern,
+            persian_digits: false,
+            locale: Locale::Latin,
+            era: Era::SolarHijri,
+        }
+    }
+}
+
+impl<'a> JalaliFormat<'a> {
+    /// Formats the local wall-clock date and time of `datetime`; `%Z` is the
+    /// offset's own name (e.g. `+0330` or `IRST`).
+    pub fn from_datetime<Tz: TimeZone>(
+        datetime: &DateTime<Tz>,
+        pattern: &'a str,
+    ) -> Result<JalaliFormat<'a>, JalaliError>
+    where
+        Tz::Offset: fmt::Display,
+    {
+        JalaliDateTime::from_datetime(datetime.clone()).map(|datetime| datetime.format(pattern))
+    }
+
+    pub fn with_time(mut self, time: NaiveTime) -> JalaliFormat<'a> {
+        self.time = time;
+        self
+    }
+
+    pub fn with_zone(mut self, offset: FixedOffset, name: impl Into<String>) -> JalaliFormat<'a> {
+        self.offset = offset;
+        self.zone_name = name.into();
+        self
+    }
+
+    /// Writes numbers with Persian digits (۰-۹).
+    pub fn persian_digits(mut self, enabled: bool) -> JalaliFormat<'a> {
+        self.persian_digits = enabled;
+        self
+    }
+
+    /// Writes month and weekday names in Persian script; shorthand for
+    /// `Locale::Persian` names without changing the digits.
+    pub fn persian_names(mut self, enabled: bool) -> JalaliFormat<'a> {
+        self.locale = if enabled {
+            Locale::Persian
+        } else {
+            Locale::Latin
+        };
+        self
+    }
+
+    /// Writes names in `locale`, and its digits unless `persian_digits` is
+    /// set afterwards.
+    pub fn locale(mut self, locale: Locale) -> JalaliFormat<'a> {
+        self.locale = locale;
+        self.persian_digits = locale.native_digits();
+        self
+    }
+
+    /// Counts `%Y`, `%C`, `%y` and `%EY` in `era`.
+    pub fn era(mut self, era: Era) -> JalaliFormat<'a> {
+        self.era = era;
+        self
+    }
+
+    fn weekday_index(&self) -> usize {
+        // Saturday-based, as in `WEEK_DAYS_AB`.
+        ((self.date.weekday().num_days_from_sunday() + 1) % 7) as usize
+    }
+
+    fn unix_seconds(&self) -> i64 {
+        (self.date.to_jdn() - UNIX_EPOCH_JDN) * SECONDS_PER_DAY
+            + self.time.num_seconds_from_midnight() as i64
+            - self.offset.local_minus_utc() as i64
+    }
+
+    fn offset_string(&self, colon: bool) -> String {
+        let seconds = self.offset.local_minus_utc();
+        let sign = if seconds < 0 { '-' } else { '+' };
+        let minutes = seconds.abs() / 60;
+        if colon {
+            format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+        } else {
+            format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+        }
+    }
+
+    // Expands one conversion character; `None` means it is not a known
+    // specifier.
+    fn field(&self, spec: char, colon: bool) -> Option<Field> {
+        let date = &self.date;
+        let month = date.month() as usize - 1;
+        let year = date.era_year(self.era);
+        let hour12 = match self.time.hour() % 12 {
+            0 => 12,
+            h => h,
+        };
+        let field = match spec {
+            'Y' => Field::number(year as i64, 4, '0'),
+            'C' => Field::number(year.div_euclid(100) as i64, 2, '0'),
+            'y' => Field::number(year.rem_euclid(100) as i64, 2, '0'),
+            'm' => Field::number(date.month() as i64, 2, '0'),
+            'd' => Field::number(date.day() as i64, 2, '0'),
+            'e' => Field::number(date.day() as i64, 2, ' '),
+            'j' => Field::number(date.ordinal() as i64, 3, '0'),
+            'u' => Field::number(self.weekday_index() as i64 + 1, 1, '0'),
+            'w' => Field::number(self.weekday_index() as i64, 1, '0'),
+            'H' => Field::number(self.time.hour() as i64, 2, '0'),
+            'k' => Field::number(self.time.hour() as i64, 2, ' '),
+            'I' => Field::number(hour12 as i64, 2, '0'),
+            'l' => Field::number(hour12 as i64, 2, ' '),
+            'M' => Field::number(self.time.minute() as i64, 2, '0'),
+            'S' => Field::number(self.time.second() as i64, 2, '0'),
+            'N' => Field::number(self.time.nanosecond() as i64, 9, '0'),
+            's' => Field::number(self.unix_seconds(), 1, '0'),
+            'B' => Field::text(self.locale.month_names()[month]),
+            'b' | 'h' => Field::text(self.locale.month_names_abbr()[month]),
+            'A' => Field::text(self.locale.weekday_names()[self.weekday_index()]),
+            'a' => Field::text(self.locale.weekday_names_abbr()[self.weekday_index()]),
+            'p' => Field::text(if self.time.hour() < 12 { "AM" } else { "PM" }),
+            'P' => Field::text(if self.time.hour() < 12 { "am" } else { "pm" }),
+            'z' => Field::text(self.offset_string(colon)),
+            'Z' => Field::text(self.zone_name.clone()),
+            'D' => Field::composite(self.expand("%Y/%m/%d")),
+            'F' => Field::composite(self.expand("%Y-%m-%d")),
+            'T' => Field::composite(self.expand("%H:%M:%S")),
+            'R' => Field::composite(self.expand("%H:%M")),
+            'c' => Field::composite(self.expand("%a %B %d %T %Z %-Y")),
+            'n' => Field::text("\n"),
+            't' => Field::text("\t"),
+            '%' => Field::text("%"),
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    // The `%E` alternative forms.
+    fn era_field(&self, spec: char) -> Option<Field> {
+        match spec {
+            'C' => Some(Field::text(self.era.name(self.locale))),
+            'Y' => Some(Field::composite(self.expand("%Y %EC"))),
+            _ => None,
+        }
+    }
+
+    // The `~` forms, spelled out in words.
+    fn words_field(&self, spec: char) -> Option<Field> {
+        let persian = self.locale.is_persian_language() && self.locale.is_arabic_script();
+        match (spec, persian) {
+            ('Y', true) => Some(Field::text(persian_cardinal(self.words_year()))),
+            ('Y', false) => Some(Field::text(english_cardinal(self.words_year()))),
+            ('d' | 'e', true) => Some(Field::text(persian_ordinal(self.date.day().into()))),
+            ('d' | 'e', false) => Some(Field::text(english_ordinal(self.date.day().into()))),
+            ('D', true) => Some(Field::text(self.expand("%~d %B %~Y"))),
+            ('D', false) => Some(Field::text(self.expand("%~d of %B, %~Y"))),
+            _ => None,
+        }
+    }
+
+    fn words_year(&self) -> i64 {
+        self.date.era_year(self.era).into()
+    }
+
+    fn expand(&self, pattern: &str) -> String {
+        let mut out = String::new();
+        self.write_pattern(pattern, &mut out);
+        out
+    }
+
+    fn write_pattern(&self, pattern: &str, out: &mut String) {
+        let mut rest = pattern;
+        while let Some(start) = rest.find('%') {
+            out.push_str(&rest[..start]);
+            let spec = &rest[start..];
+            match self.write_spec(spec, out) {
+                Some(consumed) => rest = &spec[consumed..],
+                None => {
+                    out.push('%');
+                    rest = &spec[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+    }
+
+    // Writes the specifier at the start of `spec` (which begins with '%')
+    // and returns how many bytes it used, or `None` if it is not a valid one.
+    fn write_spec(&self, spec: &str, out: &mut String) -> Option<usize> {
+        let mut chars = spec.char_indices().skip(1).peekable();
+        let mut pad_override = None;
+        let mut uppercase = false;
+        let mut words = false;
+        while let Some(&(_, c)) = chars.peek() {
+            match c {
+                '-' => pad_override = Some(Padding::None),
+                '_' => pad_override = Some(Padding::With(' ')),
+                '0' => pad_override = Some(Padding::With('0')),
+                '^' => uppercase = true,
+                '~' => words = true,
+                _ => break,
+            }
+            chars.next();
+        }
+        let mut width = None;
+        while let Some(&(_, c)) = chars.peek() {
+            let Some(digit) = c.to_digit(10) else { break };
+            width = Some(width.unwrap_or(0) * 10 + digit as usize);
+            chars.next();
+        }
+        let mut colon = false;
+        if let Some(&(_, ':')) = chars.peek() {
+            colon = true;
+            chars.next();
+        }
+        let (mut index, mut conversion) = chars.next()?;
+        if colon && conversion != 'z' {
+            return None;
+        }
+        let field = if words {
+            self.words_field(conversion)?
+        } else if conversion == 'E' {
+            (index, conversion) = chars.next()?;
+            self.era_field(conversion)?
+        } else {
+            self.field(conversion, colon)?
+        };
+        let text = field.render(pad_override, width);
+        let text = if uppercase { text.to_uppercase() } else { text };
+        if self.persian_digits && field.is_numeric() {
+            out.push_str(&to_persian_numerals(&text));
+        } else {
+            out.push_str(&text);
+        }
+        Some(index + conversion.len_utf8())
+    }
+}
+
+impl fmt::Display for JalaliFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expand(self.pattern))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Padding {
+    None,
+    With(char),
+}
+
+enum Field {
+    Number { value: i64, width: usize, pad: char },
+    Text(String),
+    // Already-expanded composite such as `%T`; digits in it are numbers.
+    Composite(String),
+}
+
+impl Field {
+    fn number(value: i64, width: usize, pad: char) -> Field {
+        Field::Number { value, width, pad }
+    }
+
+    fn text(text: impl Into<String>) -> Field {
+        Field::Text(text.into())
+    }
+
+    fn composite(text: String) -> Field {
+        Field::Composite(text)
+    }
+
+    fn is_numeric(&self) -> bool {
+        !matches!(self, Field::Text(_))
+    }
+
+    fn render(&self, pad_override: Option<Padding>, width: Option<usize>) -> String {
+        match self {
+            Field::Number {
+                value,
+                width: default_width,
+                pad,
+            } => {
+                let digits = value.unsigned_abs().to_string();
+                let sign = if *value < 0 { "-" } else { "" };
+                let padding = pad_override.unwrap_or(Padding::With(*pad));
+                let width = width.unwrap_or(*default_width);
+                match padding {
+                    Padding::None => format!("{}{}", sign, digits),
+                    Padding::With('0') => format!(
+                        "{}{:0>width$}",
+                        sign,
+                        digits,
+                        width = width.saturating_sub(sign.len())
+                    ),
+                    Padding::With(c) => pad_left(&format!("{}{}", sign, digits), width, c),
+                }
+            }
+            Field::Text(text) | Field::Composite(text) => match (pad_override, width) {
+                (Some(Padding::With(c)), Some(width)) => pad_left(text, width, c),
+                (_, Some(width)) => pad_left(text, width, ' '),
+                _ => text.clone(),
+            },
+        }
+    }
+}
+
+fn pad_left(text: &str, width: usize, pad: char) -> String {
+    let len = text.chars().count();
+    let mut out: String = std::iter::repeat_n(pad, width.saturating_sub(len)).collect();
+    out.push_str(text);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+    use chrono_tz::Asia::Tehran;
+
+    #[test]
+    fn test_date_specifiers() {
+        // 7 Mordad 1403 was a Sunday (2024-07-28).
+        let d = ymd(1403, 5, 7);
+        assert_eq!(d.format("%Y/%m/%d").to_string(), "1403/05/07");
+        assert_eq!(d.format("%D|%F").to_string(), "1403/05/07|1403-05-07");
+        assert_eq!(
+            d.format("%A %e %B %Y").to_string(),
+            "Yekshanbeh  7 Mordad 1403"
+        );
+        assert_eq!(d.format("%a %b %-d %y %C").to_string(), "Yek Mor 7 03 14");
+        assert_eq!(d.format("%j %u %w").to_string(), "131 2 1");
+        assert_eq!(ymd(1403, 12, 30).format("%j").to_string(), "366");
+        assert_eq!(ymd(5, 1, 1).format("%Y").to_string(), "0005");
+    }
+
+    #[test]
+    fn test_padding_and_case_modifiers() {
+        let d = ymd(1403, 1, 5);
+        assert_eq!(d.format("%-m/%-d").to_string(), "1/5");
+        assert_eq!(d.format("%_m|%_3d").to_string(), " 1|  5");
+        assert_eq!(d.format("%0e").to_string(), "05");
+        assert_eq!(d.format("%^B").to_string(), "FARVARDIN");
+        assert_eq!(d.format("[%12B]").to_string(), "[   Farvardin]");
+        assert_eq!(d.format("%5Y").to_string(), "01403");
+    }
+
+    #[test]
+    fn test_time_and_zone() {
+        let time = NaiveTime::from_hms_nano_opt(21, 4, 9, 120).unwrap();
+        let offset = FixedOffset::east_opt(3 * 3600 + 1800).unwrap();
+        let f = ymd(1403, 5, 7)
+            .format("%T %R %I:%M %p %P %l %N %z %:z %Z")
+            .with_time(time)
+            .with_zone(offset, "IRST");
+        assert_eq!(
+            f.to_string(),
+            "21:04:09 21:04 09:04 PM pm  9 000000120 +0330 +03:30 IRST"
+        );
+
+        assert_eq!(
+            ymd(1348, 10, 11).format("%s %T %z %Z").to_string(),
+            "0 00:00:00 +0000 UTC"
+        );
+    }
+
+    #[test]
+    fn test_from_datetime() {
+        let dt = Tehran.with_ymd_and_hms(2025, 3, 20, 12, 31, 30).unwrap();
+        let f = JalaliFormat::from_datetime(&dt, "%c|%s").unwrap();
+        assert_eq!(
+            f.to_string(),
+            "Pan Esfand 30 12:31:30 +0330 1403|1742461290"
+        );
+        assert_eq!(
+            ymd(622, 1, 1).format("%c").to_string(),
+            "Sha Farvardin 01 00:00:00 UTC 622"
+        );
+    }
+
+    #[test]
+    fn test_persian_output() {
+        let f = ymd(1403, 5, 7)
+            .format("%A %-d %B %Y %T")
+            .persian_digits(true)
+            .persian_names(true);
+        assert_eq!(f.to_string(), "یکشنبه ۷ مرداد ۱۴۰۳ ۰۰:۰۰:۰۰");
+    }
+
+    #[test]
+    fn test_written_date() {
+        let d = ymd(1403, 9, 25);
+        assert_eq!(
+            d.format("%~D").locale(Locale::Persian).to_string(),
+            "بیست و پنجم آذر هزار و چهارصد و سه"
+        );
+        assert_eq!(
+            d.format("%~D").to_string(),
+            "twenty-fifth of Azar, one thousand four hundred and three"
+        );
+        assert_eq!(
+            d.format("%~d %~Y").era(Era::Imperial).to_string(),
+            "twenty-fifth two thousand five hundred and eighty-three"
+        );
+        assert_eq!(
+            d.format("%~Y").locale(Locale::Pashto).to_string(),
+            "one thousand four hundred and three"
+        );
+        assert_eq!(d.format("%^~d").to_string(), "TWENTY-FIFTH");
+        assert_eq!(d.format("%~m").to_string(), "%~m");
+        assert_eq!(d.format("%Od").to_string(), "%Od");
+    }
+
+    #[test]
+    fn test_literals_and_unknown_specifiers() {
+        let d = ymd(1403, 5, 7);
+        assert_eq!(d.format("100%% on %d%n").to_string(), "100% on 07\n");
+        assert_eq!(d.format("%Q %:d %").to_string(), "%Q %:d %");
+        assert_eq!(d.format("no specifiers").to_string(), "no specifiers");
+    }
+}
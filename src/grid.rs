@@ -0,0 +1,251 @@
+//! Calendar page layouts: a month as rows of seven day cells and a year as
+//! twelve such months.
+//!
+//! Columns run from Saturday to Friday as in `scal`. The grids only place
+//! the days and flag them; drawing them is left to the frontend.
+
+use chrono::NaiveDate;
+
+use crate::{
+    HolidayCalendar, JALALI_FRIDAY_INDEX, JalaliDate, JalaliDateRange, JalaliError,
+    MONTHS_PER_YEAR_COUNT, WEEK_DAYS_TOTAL, first_weekday,
+};
+
+/// One row of a month grid, Saturday first; `None` for the cells before
+/// the first and after the last day of the month.
+pub type GridWeek = [Option<DayCell>; WEEK_DAYS_TOTAL as usize];
+
+/// A day placed in a month grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DayCell {
+    date: JalaliDate,
+    gregorian: Option<NaiveDate>,
+    weekend: bool,
+    today: bool,
+    holiday: bool,
+}
+
+impl DayCell {
+    pub fn date(&self) -> JalaliDate {
+        self.date
+    }
+
+    /// The same day on the Gregorian calendar, if the grid was built
+    /// `with_gregorian`.
+    pub fn gregorian(&self) -> Option<NaiveDate> {
+        self.gregorian
+    }
+
+    /// Whether the day is in the Friday column.
+    pub fn is_weekend(&self) -> bool {
+        self.weekend
+    }
+
+    pub fn is_today(&self) -> bool {
+        self.today
+    }
+
+    /// Whether the calendar given to `with_holidays` has a holiday on it.
+    pub fn is_holiday(&self) -> bool {
+        self.holiday
+    }
+}
+
+/// The days of a Jalali month laid out in weeks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MonthGrid {
+    year: i32,
+    month: u8,
+    weeks: Vec<GridWeek>,
+}
+
+impl MonthGrid {
+    pub fn new(year: i32, month: u8) -> Result<MonthGrid, JalaliError> {
+        let days = JalaliDateRange::month(year, month)?;
+        let first_column = first_weekday(year, month).expect("the range checked the month");
+
+        let mut weeks = Vec::new();
+        let mut week: GridWeek = [None; WEEK_DAYS_TOTAL as usize];
+        let mut column = first_column as usize;
+        for date in days {
+            week[column] = Some(DayCell {
+                date,
+                gregorian: None,
+                weekend: column == JALALI_FRIDAY_INDEX,
+                today: false,
+                holiday: false,
+            });
+            column += 1;
+            if column == WEEK_DAYS_TOTAL as usize {
+                weeks.push(week);
+                week = [None; WEEK_DAYS_TOTAL as usize];
+                column = 0;
+            }
+        }
+        if column != 0 {
+            weeks.push(week);
+        }
+        Ok(MonthGrid { year, month, weeks })
+    }
+
+    /// Fills in the Gregorian date of every day.
+    pub fn with_gregorian(mut self) -> MonthGrid {
+        self.update(|cell| cell.gregorian = Some(cell.date.to_gregorian()));
+        self
+    }
+
+    /// Flags `today` if it falls in this month.
+    pub fn with_today(mut self, today: JalaliDate) -> MonthGrid {
+        self.update(|cell| cell.today = cell.date == today);
+        self
+    }
+
+    /// Flags the days on which `calendar` has a holiday.
+    pub fn with_holidays(self, calendar: &HolidayCalendar) -> MonthGrid {
+        let dates = holiday_dates(calendar, self.year);
+        self.with_holiday_dates(&dates)
+    }
+
+    fn with_holiday_dates(mut self, dates: &[JalaliDate]) -> MonthGrid {
+        self.update(|cell| cell.holiday = dates.contains(&cell.date));
+        self
+    }
+
+    fn update(&mut self, mut f: impl FnMut(&mut DayCell)) {
+        for cell in self.weeks.iter_mut().flatten().flatten() {
+            f(cell);
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The rows of the grid; four to six of them.
+    pub fn weeks(&self) -> &[GridWeek] {
+        &self.weeks
+    }
+
+    /// Column (0 = Saturday ... 6 = Friday) of the first day.
+    pub fn first_column(&self) -> usize {
+        self.weeks[0]
+            .iter()
+            .take_while(|cell| cell.is_none())
+            .count()
+    }
+
+    /// The days of the month in order.
+    pub fn days(&self) -> impl Iterator<Item = &DayCell> {
+        self.weeks.iter().flatten().flatten()
+    }
+}
+
+/// The twelve month grids of a Jalali year.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct YearGrid {
+    year: i32,
+    months: Vec<MonthGrid>,
+}
+
+impl YearGrid {
+    pub fn new(year: i32) -> Result<YearGrid, JalaliError> {
+        let months = (1..=MONTHS_PER_YEAR_COUNT)
+            .map(|month| MonthGrid::new(year, month))
+            .collect::<Result<_, _>>()?;
+        Ok(YearGrid { year, months })
+    }
+
+    pub fn with_gregorian(self) -> YearGrid {
+        self.map(MonthGrid::with_gregorian)
+    }
+
+    pub fn with_today(self, today: JalaliDate) -> YearGrid {
+        self.map(|month| month.with_today(today))
+    }
+
+    pub fn with_holidays(self, calendar: &HolidayCalendar) -> YearGrid {
+        let dates = holiday_dates(calendar, self.year);
+        self.map(|month| month.with_holiday_dates(&dates))
+    }
+
+    fn map(self, f: impl FnMut(MonthGrid) -> MonthGrid) -> YearGrid {
+        YearGrid {
+            year: self.year,
+            months: self.months.into_iter().map(f).collect(),
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Farvardin to Esfand.
+    pub fn months(&self) -> &[MonthGrid] {
+        &self.months
+    }
+
+    /// The months in rows of `months_per_row` for a page layout, e.g. 3 for
+    /// the four rows of `scal -y`.
+    pub fn rows(&self, months_per_row: usize) -> impl Iterator<Item = &[MonthGrid]> {
+        self.months.chunks(months_per_row.max(1))
+    }
+}
+
+// The holidays of `year`, listed once for a whole grid.
+fn holiday_dates(calendar: &HolidayCalendar, year: i32) -> Vec<JalaliDate> {
+    calendar
+        .holidays_in(year)
+        .expect("the year of a grid is valid")
+        .iter()
+        .map(|holiday| holiday.date())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_month_grid() {
+        // 1 Mordad 1403 was a Monday, the third column.
+        let grid = MonthGrid::new(1403, 5)
+            .unwrap()
+            .with_today(ymd(1403, 5, 12));
+        assert_eq!(grid.first_column(), 2);
+        assert_eq!(grid.weeks().len(), 5);
+        assert_eq!(grid.days().count(), 31);
+        assert_eq!(grid.days().next().unwrap().date(), ymd(1403, 5, 1));
+
+        let friday = grid.weeks()[1][JALALI_FRIDAY_INDEX].unwrap();
+        assert_eq!(friday.date(), ymd(1403, 5, 12));
+        assert!(friday.is_weekend() && friday.is_today());
+        assert_eq!(grid.days().filter(|cell| cell.is_today()).count(), 1);
+        assert_eq!(friday.gregorian(), None);
+        assert!(grid.weeks()[4][6].is_none());
+
+        assert!(MonthGrid::new(1403, 13).is_err());
+    }
+
+    #[test]
+    fn test_year_grid_flags() {
+        let grid = YearGrid::new(1403)
+            .unwrap()
+            .with_gregorian()
+            .with_holidays(&HolidayCalendar::iran());
+        assert_eq!(grid.months().len(), 12);
+        assert_eq!(grid.rows(3).count(), 4);
+        assert_eq!(grid.rows(3).nth(1).unwrap()[0].month(), 4);
+
+        let nowruz = grid.months()[0].days().next().unwrap();
+        assert!(nowruz.is_holiday());
+        assert_eq!(nowruz.gregorian(), NaiveDate::from_ymd_opt(2024, 3, 20));
+        let esfand = &grid.months()[11];
+        assert_eq!(esfand.days().count(), 30);
+        assert!(esfand.days().all(|cell| cell.gregorian().is_some()));
+    }
+}
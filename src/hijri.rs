@@ -0,0 +1,449 @@
+//! Lunar Hijri (Islamic, Qamari) calendar.
+//!
+//! Conversions go through the Julian Day Number, like the Jalali ones.  A
+//! rule only has to say on which day each month starts:
+//!
+//! - `TabularHijriRule` alternates 30- and 29-day months and adds a day to
+//!   the last month in 11 years of every 30.
+//! - `HijriTable` holds officially observed month starts, which can differ
+//!   from the tabular ones by a day or two, and falls back to the tabular
+//!   rule for months it does not list.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    GREGORIAN_CE_JDN_OFFSET, JalaliDate, JalaliError, LAST_MONTH_INDEX, compute_jdn_internal,
+    try_gregorian_date,
+};
+
+pub(crate) const HIJRI_EPOCH_JDN: i64 = 1_948_440; // 1 Muharram 1 = 622-07-16 Julian
+pub(crate) const HIJRI_CYCLE_YEARS: i64 = 30;
+pub(crate) const DAYS_IN_HIJRI_CYCLE: i64 = 10_631;
+pub(crate) const DAYS_IN_HIJRI_LEAP_YEAR: i64 = 355;
+
+pub trait HijriRule {
+    /// Julian Day Number of the first day of `month` of Hijri year `year`.
+    fn month_start_jdn(&self, year: i32, month: u8) -> i64;
+
+    /// Falls back to the tabular length for the last month of `i32::MAX`,
+    /// whose following month cannot be named.
+    fn days_in_month(&self, year: i32, month: u8) -> u8 {
+        match next_month(year, month) {
+            Some((next_year, next_month)) => {
+                (self.month_start_jdn(next_year, next_month) - self.month_start_jdn(year, month))
+                    as u8
+            }
+            None => hijri_days_in_month(year, month),
+        }
+    }
+
+    fn is_leap(&self, year: i32) -> bool {
+        match year.checked_add(1) {
+            Some(next_year) => {
+                self.month_start_jdn(next_year, 1) - self.month_start_jdn(year, 1)
+                    == DAYS_IN_HIJRI_LEAP_YEAR
+            }
+            None => is_hijri_leap(year),
+        }
+    }
+
+    /// The Hijri year and month that contain `jdn`.
+    fn month_of_jdn(&self, jdn: i64) -> (i32, u8) {
+        // Start from the tabular month, which is at most a month off.
+        let (mut year, mut month, _) = jdn_to_hijri(jdn);
+        while self.month_start_jdn(year, month) > jdn {
+            (year, month) = previous_month(year, month);
+        }
+        while let Some((next_year, next_month)) = next_month(year, month) {
+            if self.month_start_jdn(next_year, next_month) > jdn {
+                break;
+            }
+            (year, month) = (next_year, next_month);
+        }
+        (year, month)
+    }
+}
+
+/// The arithmetical Hijri calendar (30-year cycle with leap years 2, 5, 7,
+/// 10, 13, 16, 18, 21, 24, 26 and 29, epoch 16 July 622 Julian) used by
+/// every function in this crate unless another rule is passed in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TabularHijriRule;
+
+impl HijriRule for TabularHijriRule {
+    fn month_start_jdn(&self, year: i32, month: u8) -> i64 {
+        hijri_to_jdn(year, month, 1)
+    }
+
+    fn days_in_month(&self, year: i32, month: u8) -> u8 {
+        hijri_days_in_month(year, month)
+    }
+
+    fn is_leap(&self, year: i32) -> bool {
+        is_hijri_leap(year)
+    }
+
+    fn month_of_jdn(&self, jdn: i64) -> (i32, u8) {
+        let (year, month, _) = jdn_to_hijri(jdn);
+        (year, month)
+    }
+}
+
+/// Observed month starts, with the tabular calendar for the months not
+/// listed.  An empty table is the tabular calendar.
+///
+/// A table is read from text, one month per line, giving the Hijri year and
+/// month and the Jalali date of its first day:
+///
+/// ```text
+/// # Muharram and Safar 1446 as observed in Iran.
+/// 1446/01 1403/04/17
+/// 1446/02 1403/05/16
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HijriTable {
+    starts: BTreeMap<(i32, u8), i64>,
+}
+
+impl HijriTable {
+    /// Reads month starts in the format described above.
+    pub fn parse(text: &str) -> Result<HijriTable, JalaliError> {
+        HijriTable::default().with_overrides(text)
+    }
+
+    /// Adds or replaces the month starts listed in `text`.
+    ///
+    /// Every month next to a listed one must still have 29 or 30 days, so
+    /// moving the start of a month usually means listing the following
+    /// month too.
+    pub fn with_overrides(mut self, text: &str) -> Result<HijriTable, JalaliError> {
+        let mut listed = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |reason: &str| JalaliError::HijriTable {
+                line: index + 1,
+                reason: reason.to_string(),
+            };
+
+            let (month, start) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| err("expected a YYYY/MM month and a Jalali date"))?;
+            let (year, month) = month
+                .split_once('/')
+                .ok_or_else(|| err("expected a YYYY/MM month"))?;
+            let year = year
+                .parse::<i32>()
+                .ok()
+                .filter(|&year| year >= 1)
+                .ok_or_else(|| err("year should be 1 or later"))?;
+            let month = month
+                .parse::<u8>()
+                .ok()
+                .filter(|month| (1..=LAST_MONTH_INDEX).contains(month))
+                .ok_or_else(|| err("month should be 1-12"))?;
+            let start = start
+                .trim()
+                .parse::<JalaliDate>()
+                .map_err(|e| err(&e.to_string()))?;
+
+            self.starts.insert((year, month), start.to_jdn());
+            listed.push((index + 1, year, month));
+        }
+
+        for (line, year, month) in listed {
+            for (year, month) in [previous_month(year, month), (year, month)] {
+                let Some((next_year, next_month)) = next_month(year, month) else {
+                    return Err(JalaliError::HijriTable {
+                        line,
+                        reason: "year is too large".to_string(),
+                    });
+                };
+                let days =
+                    self.month_start_jdn(next_year, next_month) - self.month_start_jdn(year, month);
+                if !(29..=30).contains(&days) {
+                    return Err(JalaliError::HijriTable {
+                        line,
+                        reason: format!("month {}/{:02} would have {} days", year, month, days),
+                    });
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+impl HijriRule for HijriTable {
+    fn month_start_jdn(&self, year: i32, month: u8) -> i64 {
+        self.starts
+            .get(&(year, month))
+            .copied()
+            .unwrap_or_else(|| hijri_to_jdn(year, month, 1))
+    }
+}
+
+pub fn try_hijri_to_jalali(hy: i32, hm: u8, hd: u8) -> Result<(i32, u8, u8), JalaliError> {
+    try_hijri_to_jalali_with(&TabularHijriRule, hy, hm, hd)
+}
+
+pub fn try_hijri_to_jalali_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    hy: i32,
+    hm: u8,
+    hd: u8,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let date = JalaliDate::try_from_jdn(hijri_to_jdn_with(rule, hy, hm, hd)?)?;
+    Ok((date.year(), date.month(), date.day()))
+}
+
+pub fn hijri_to_jalali(hy: i32, hm: u8, hd: u8) -> (i32, u8, u8) {
+    try_hijri_to_jalali(hy, hm, hd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+pub fn try_jalali_to_hijri(jy: i32, jm: u8, jd: u8) -> Result<(i32, u8, u8), JalaliError> {
+    try_jalali_to_hijri_with(&TabularHijriRule, jy, jm, jd)
+}
+
+pub fn try_jalali_to_hijri_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<(i32, u8, u8), JalaliError> {
+    jdn_to_hijri_with(rule, JalaliDate::try_from_ymd(jy, jm, jd)?.to_jdn())
+}
+
+pub fn jalali_to_hijri(jy: i32, jm: u8, jd: u8) -> (i32, u8, u8) {
+    try_jalali_to_hijri(jy, jm, jd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+pub fn try_hijri_to_gregorian(hy: i32, hm: u8, hd: u8) -> Result<(i32, u32, u32), JalaliError> {
+    try_hijri_to_gregorian_with(&TabularHijriRule, hy, hm, hd)
+}
+
+pub fn try_hijri_to_gregorian_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    hy: i32,
+    hm: u8,
+    hd: u8,
+) -> Result<(i32, u32, u32), JalaliError> {
+    let jdn = hijri_to_jdn_with(rule, hy, hm, hd)?;
+    let gregorian = i32::try_from(jdn - GREGORIAN_CE_JDN_OFFSET)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or(JalaliError::OutOfRange)?;
+    Ok((gregorian.year(), gregorian.month(), gregorian.day()))
+}
+
+pub fn hijri_to_gregorian(hy: i32, hm: u8, hd: u8) -> (i32, u32, u32) {
+    try_hijri_to_gregorian(hy, hm, hd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+pub fn try_gregorian_to_hijri(gy: i32, gm: u32, gd: u32) -> Result<(i32, u8, u8), JalaliError> {
+    try_gregorian_to_hijri_with(&TabularHijriRule, gy, gm, gd)
+}
+
+pub fn try_gregorian_to_hijri_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    gy: i32,
+    gm: u32,
+    gd: u32,
+) -> Result<(i32, u8, u8), JalaliError> {
+    try_gregorian_date(gy, gm, gd)?;
+    jdn_to_hijri_with(rule, compute_jdn_internal(gy, gm, gd))
+}
+
+pub fn gregorian_to_hijri(gy: i32, gm: u32, gd: u32) -> (i32, u8, u8) {
+    try_gregorian_to_hijri(gy, gm, gd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+fn hijri_to_jdn_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    hy: i32,
+    hm: u8,
+    hd: u8,
+) -> Result<i64, JalaliError> {
+    if hy < 1 {
+        return Err(JalaliError::OutOfRange);
+    }
+    if hm == 0 || hm > LAST_MONTH_INDEX {
+        return Err(JalaliError::InvalidMonth { month: hm as u32 });
+    }
+    if hd == 0 || hd > rule.days_in_month(hy, hm) {
+        return Err(JalaliError::InvalidDay {
+            year: hy,
+            month: hm as u32,
+            day: hd as u32,
+        });
+    }
+    Ok(rule.month_start_jdn(hy, hm) + hd as i64 - 1)
+}
+
+fn jdn_to_hijri_with<R: HijriRule + ?Sized>(
+    rule: &R,
+    jdn: i64,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let (year, month) = rule.month_of_jdn(jdn);
+    if year < 1 {
+        return Err(JalaliError::OutOfRange);
+    }
+    let day = jdn - rule.month_start_jdn(year, month) + 1;
+    Ok((year, month, day as u8))
+}
+
+// `None` after the last month of `i32::MAX`.
+fn next_month(year: i32, month: u8) -> Option<(i32, u8)> {
+    if month == LAST_MONTH_INDEX {
+        Some((year.checked_add(1)?, 1))
+    } else {
+        Some((year, month + 1))
+    }
+}
+
+fn previous_month(year: i32, month: u8) -> (i32, u8) {
+    if month == 1 {
+        (year - 1, LAST_MONTH_INDEX)
+    } else {
+        (year, month - 1)
+    }
+}
+
+pub(crate) fn is_hijri_leap(year: i32) -> bool {
+    (14 + 11 * year as i64).rem_euclid(HIJRI_CYCLE_YEARS) < 11
+}
+
+pub(crate) fn hijri_days_in_month(year: i32, month: u8) -> u8 {
+    if month % 2 == 1 || (month == LAST_MONTH_INDEX && is_hijri_leap(year)) {
+        30
+    } else {
+        29
+    }
+}
+
+pub(crate) fn hijri_to_jdn(year: i32, month: u8, day: u8) -> i64 {
+    let year = year as i64;
+    let month = month as i64;
+    // Months before `month` alternate 30 and 29 days.
+    let days_before_month = (59 * (month - 1) + 1) / 2;
+    let leap_days_before_year = (3 + 11 * year).div_euclid(HIJRI_CYCLE_YEARS);
+    HIJRI_EPOCH_JDN - 1 + day as i64 + days_before_month + (year - 1) * 354 + leap_days_before_year
+}
+
+pub(crate) fn jdn_to_hijri(jdn: i64) -> (i32, u8, u8) {
+    let year = (HIJRI_CYCLE_YEARS * (jdn - HIJRI_EPOCH_JDN) + 10_646)
+        .div_euclid(DAYS_IN_HIJRI_CYCLE) as i32;
+    let month = (1..=LAST_MONTH_INDEX)
+        .rev()
+        .find(|&month| jdn >= hijri_to_jdn(year, month, 1))
+        .unwrap_or(1);
+    let day = jdn - hijri_to_jdn(year, month, 1) + 1;
+    (year, month, day as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jalali_to_jdn_internal;
+
+    #[test]
+    fn test_tabular_dates() {
+        // 1 Muharram 1445 was 28 Tir 1402 (19 July 2023).
+        assert_eq!(
+            hijri_to_jdn(1445, 1, 1),
+            jalali_to_jdn_internal(1402, 4, 28)
+        );
+        assert_eq!(
+            jdn_to_hijri(jalali_to_jdn_internal(1402, 4, 28)),
+            (1445, 1, 1)
+        );
+        // 1445 is a leap year, so 1446 began 355 days later.
+        assert_eq!(
+            hijri_to_jdn(1446, 1, 1),
+            jalali_to_jdn_internal(1403, 4, 18)
+        );
+        assert_eq!(jdn_to_hijri(HIJRI_EPOCH_JDN), (1, 1, 1));
+
+        let leap_years = (1..=30)
+            .filter(|&year| is_hijri_leap(year))
+            .collect::<Vec<_>>();
+        assert_eq!(leap_years, [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let start = hijri_to_jdn(1440, 1, 1);
+        for jdn in start..start + 3 * DAYS_IN_HIJRI_CYCLE {
+            let (year, month, day) = jdn_to_hijri(jdn);
+            assert!(day >= 1 && day <= hijri_days_in_month(year, month));
+            assert_eq!(hijri_to_jdn(year, month, day), jdn);
+        }
+    }
+
+    #[test]
+    fn test_free_conversions() {
+        assert_eq!(try_jalali_to_hijri(1402, 4, 28), Ok((1445, 1, 1)));
+        assert_eq!(hijri_to_jalali(1445, 1, 1), (1402, 4, 28));
+        assert_eq!(gregorian_to_hijri(2023, 7, 19), (1445, 1, 1));
+        assert_eq!(hijri_to_gregorian(1445, 1, 1), (2023, 7, 19));
+        assert_eq!(
+            try_hijri_to_jalali(1445, 2, 30),
+            Err(JalaliError::InvalidDay {
+                year: 1445,
+                month: 2,
+                day: 30
+            })
+        );
+        assert_eq!(
+            try_hijri_to_jalali(1445, 13, 1),
+            Err(JalaliError::InvalidMonth { month: 13 })
+        );
+        // 1 Farvardin 1 is before the Hijri epoch.
+        assert_eq!(try_jalali_to_hijri(1, 1, 1), Err(JalaliError::OutOfRange));
+        assert_eq!(
+            try_hijri_to_jalali_with(&HijriTable::default(), i32::MAX, 12, 1),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(HijriTable::default().days_in_month(i32::MAX, 12), 30);
+    }
+
+    #[test]
+    fn test_table() {
+        let table =
+            HijriTable::parse("# observed\n1446/01 1403/04/17\n1446/02 1403/05/16\n").unwrap();
+        assert_eq!(
+            try_jalali_to_hijri_with(&table, 1403, 4, 17),
+            Ok((1446, 1, 1))
+        );
+        // Dhu al-Hijja 1445 ended a day before the tabular date.
+        assert_eq!(table.days_in_month(1445, 12), 29);
+        assert!(!table.is_leap(1445));
+        assert_eq!(table.days_in_month(1446, 2), 30);
+        assert_eq!(
+            try_jalali_to_hijri_with(&table, 1403, 4, 16),
+            Ok((1445, 12, 29))
+        );
+        assert_eq!(
+            try_hijri_to_gregorian_with(&table, 1446, 1, 10),
+            Ok((2024, 7, 16))
+        );
+        for jdn in table.month_start_jdn(1445, 11)..table.month_start_jdn(1446, 4) {
+            let (year, month, day) = jdn_to_hijri_with(&table, jdn).unwrap();
+            assert_eq!(hijri_to_jdn_with(&table, year, month, day), Ok(jdn));
+        }
+
+        // Moving Muharram alone would leave it 31 days long.
+        assert_eq!(
+            HijriTable::parse("1446/01 1403/04/17").unwrap_err(),
+            JalaliError::HijriTable {
+                line: 1,
+                reason: "month 1446/01 would have 31 days".to_string()
+            }
+        );
+        assert!(HijriTable::parse("1446/13 1403/04/17").is_err());
+        assert!(HijriTable::parse("1446/01").is_err());
+    }
+}
@@ -0,0 +1,422 @@
+//! Official holidays; the entry format is described on `HolidayCalendar`.
+
+use std::sync::OnceLock;
+
+use crate::{HijriRule, HijriTable, JalaliDate, JalaliError, LAST_MONTH_INDEX};
+
+const IRAN_HOLIDAYS: &str = include_str!("../data/holidays.txt");
+
+/// Where a holiday comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HolidayKind {
+    /// Fixed on the Jalali calendar.
+    Solar,
+    /// Fixed on the lunar Hijri calendar.
+    Lunar,
+    /// A one-off date.
+    Announced,
+}
+
+/// A holiday on a particular date.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Holiday {
+    date: JalaliDate,
+    name: String,
+    kind: HolidayKind,
+}
+
+impl Holiday {
+    pub fn date(&self) -> JalaliDate {
+        self.date
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn kind(&self) -> HolidayKind {
+        self.kind
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LunarDay {
+    Day(u8),
+    Last,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    Solar {
+        month: u8,
+        day: u8,
+        name: String,
+    },
+    Lunar {
+        month: u8,
+        day: LunarDay,
+        name: String,
+    },
+    Announced {
+        date: JalaliDate,
+        name: String,
+    },
+    Cancelled(JalaliDate),
+}
+
+/// A set of holiday rules.
+///
+/// Rules come from a small text format, one entry per line:
+///
+/// ```text
+/// solar 01/01 Nowruz
+/// lunar 01/10 Ashura
+/// lunar 02/last Martyrdom of Imam Reza
+/// date 1404/02/05 Announced holiday
+/// cancel 1404/04/15
+/// ```
+///
+/// `solar` and `lunar` entries repeat every Jalali or lunar Hijri year,
+/// `date` adds a single Jalali date and `cancel` drops the solar and lunar
+/// holidays of a Jalali date.  Blank lines and lines starting with `#` are
+/// ignored.  The Iranian list bundled with the crate (`iran`) is
+/// `data/holidays.txt`; official announcements are layered on top of it
+/// with `with_overrides`.
+///
+/// Lunar dates follow the tabular Hijri calendar unless a `HijriTable` of
+/// observed month starts is given with `with_hijri_table`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    entries: Vec<Entry>,
+    hijri: HijriTable,
+}
+
+impl HolidayCalendar {
+    /// The bundled official holidays of Iran.
+    pub fn iran() -> HolidayCalendar {
+        iran_calendar().clone()
+    }
+
+    /// Reads holiday entries in the format described above.
+    pub fn parse(text: &str) -> Result<HolidayCalendar, JalaliError> {
+        Ok(HolidayCalendar {
+            entries: parse_entries(text)?,
+            hijri: HijriTable::default(),
+        })
+    }
+
+    /// Places lunar holidays with the observed month starts in `table`.
+    pub fn with_hijri_table(mut self, table: HijriTable) -> HolidayCalendar {
+        self.hijri = table;
+        self
+    }
+
+    /// Adds the entries of `text` to the calendar, e.g. a file of this
+    /// year's official announcements.
+    pub fn with_overrides(mut self, text: &str) -> Result<HolidayCalendar, JalaliError> {
+        self.entries.extend(parse_entries(text)?);
+        Ok(self)
+    }
+
+    /// Holidays of Jalali year `year` in date order.  A lunar holiday can
+    /// fall twice in one Jalali year.
+    pub fn holidays_in(&self, year: i32) -> Result<Vec<Holiday>, JalaliError> {
+        let first = JalaliDate::try_from_ymd(year, 1, 1)?.to_jdn();
+        let last = first + if crate::is_leap(year) { 365 } else { 364 };
+        let hijri_years = self.hijri.month_of_jdn(first).0.max(1)..=self.hijri.month_of_jdn(last).0;
+
+        let cancelled: Vec<JalaliDate> = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Cancelled(date) => Some(*date),
+                _ => None,
+            })
+            .collect();
+        let mut holidays = Vec::new();
+        for entry in &self.entries {
+            match entry {
+                Entry::Solar { month, day, name } => {
+                    if let Some(date) = JalaliDate::from_ymd_opt(year, *month, *day) {
+                        holidays.push(holiday(date, name, HolidayKind::Solar));
+                    }
+                }
+                Entry::Lunar { month, day, name } => {
+                    for hijri_year in hijri_years.clone() {
+                        let days = self.hijri.days_in_month(hijri_year, *month);
+                        let day = match *day {
+                            LunarDay::Day(day) if day > days => continue,
+                            LunarDay::Day(day) => day,
+                            LunarDay::Last => days,
+                        };
+                        let jdn = self.hijri.month_start_jdn(hijri_year, *month) + day as i64 - 1;
+                        if (first..=last).contains(&jdn) {
+                            let date = JalaliDate::try_from_jdn(jdn)?;
+                            holidays.push(holiday(date, name, HolidayKind::Lunar));
+                        }
+                    }
+                }
+                Entry::Announced { date, name } if date.year() == year => {
+                    holidays.push(holiday(*date, name, HolidayKind::Announced));
+                }
+                Entry::Announced { .. } | Entry::Cancelled(_) => {}
+            }
+        }
+        holidays.retain(|holiday| {
+            holiday.kind == HolidayKind::Announced || !cancelled.contains(&holiday.date)
+        });
+        holidays.sort_by_key(|holiday| holiday.date);
+        Ok(holidays)
+    }
+
+    /// Holidays that fall on `date`; usually none or one.
+    pub fn holidays_on(&self, date: JalaliDate) -> Vec<Holiday> {
+        self.entries_on(date)
+            .map(|(name, kind)| holiday(date, name, kind))
+            .collect()
+    }
+
+    pub fn is_holiday(&self, date: JalaliDate) -> bool {
+        self.entries_on(date).next().is_some()
+    }
+
+    // The names and kinds of the entries that fall on `date`, checked
+    // against that one date rather than listing the whole year.
+    fn entries_on(&self, date: JalaliDate) -> impl Iterator<Item = (&str, HolidayKind)> {
+        let jdn = date.to_jdn();
+        let (hijri_year, hijri_month) = self.hijri.month_of_jdn(jdn);
+        let hijri_day = jdn - self.hijri.month_start_jdn(hijri_year, hijri_month) + 1;
+        let last_hijri_day = i64::from(self.hijri.days_in_month(hijri_year, hijri_month));
+        let cancelled = self.entries.contains(&Entry::Cancelled(date));
+
+        self.entries.iter().filter_map(move |entry| match entry {
+            Entry::Solar { month, day, name }
+                if !cancelled && (*month, *day) == (date.month(), date.day()) =>
+            {
+                Some((name.as_str(), HolidayKind::Solar))
+            }
+            Entry::Lunar { month, day, name }
+                if !cancelled && hijri_year >= 1 && *month == hijri_month =>
+            {
+                let on_day = match *day {
+                    LunarDay::Day(day) => i64::from(day) == hijri_day,
+                    LunarDay::Last => hijri_day == last_hijri_day,
+                };
+                on_day.then_some((name.as_str(), HolidayKind::Lunar))
+            }
+            Entry::Announced {
+                date: announced,
+                name,
+            } if *announced == date => Some((name.as_str(), HolidayKind::Announced)),
+            _ => None,
+        })
+    }
+}
+
+impl Default for HolidayCalendar {
+    fn default() -> HolidayCalendar {
+        HolidayCalendar::iran()
+    }
+}
+
+fn iran_calendar() -> &'static HolidayCalendar {
+    static CALENDAR: OnceLock<HolidayCalendar> = OnceLock::new();
+    CALENDAR.get_or_init(|| {
+        HolidayCalendar::parse(IRAN_HOLIDAYS).expect("bundled holiday data is valid")
+    })
+}
+
+/// Official Iranian holidays of Jalali year `year`, from the bundled list.
+pub fn holidays_in(year: i32) -> Result<Vec<Holiday>, JalaliError> {
+    iran_calendar().holidays_in(year)
+}
+
+/// Whether `date` is an official Iranian holiday in the bundled list.
+/// Fridays, the weekly day off, are not counted.
+pub fn is_holiday(date: JalaliDate) -> bool {
+    iran_calendar().is_holiday(date)
+}
+
+fn holiday(date: JalaliDate, name: &str, kind: HolidayKind) -> Holiday {
+    Holiday {
+        date,
+        name: name.to_string(),
+        kind,
+    }
+}
+
+fn parse_entries(text: &str) -> Result<Vec<Entry>, JalaliError> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |reason: &str| JalaliError::HolidayData {
+            line: index + 1,
+            reason: reason.to_string(),
+        };
+
+        let mut fields = line.splitn(3, char::is_whitespace);
+        let keyword = fields.next().unwrap_or_default();
+        let date = fields.next().ok_or_else(|| err("missing date"))?;
+        let name = fields.next().map(str::trim).unwrap_or_default();
+        if keyword != "cancel" && name.is_empty() {
+            return Err(err("missing holiday name"));
+        }
+
+        let entry = match keyword {
+            "solar" | "lunar" => {
+                let (month, day) = date
+                    .split_once('/')
+                    .ok_or_else(|| err("expected a MM/DD date"))?;
+                let month = month
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|month| (1..=LAST_MONTH_INDEX).contains(month))
+                    .ok_or_else(|| err("month should be 1-12"))?;
+                let name = name.to_string();
+                if keyword == "solar" {
+                    let day = day
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|day| (1..=31).contains(day))
+                        .ok_or_else(|| err("day should be 1-31"))?;
+                    Entry::Solar { month, day, name }
+                } else {
+                    let day = match day {
+                        "last" => LunarDay::Last,
+                        day => LunarDay::Day(
+                            day.parse::<u8>()
+                                .ok()
+                                .filter(|day| (1..=30).contains(day))
+                                .ok_or_else(|| err("day should be 1-30 or \"last\""))?,
+                        ),
+                    };
+                    Entry::Lunar { month, day, name }
+                }
+            }
+            "date" | "cancel" => {
+                let date = date
+                    .parse::<JalaliDate>()
+                    .map_err(|e| err(&e.to_string()))?;
+                if keyword == "date" {
+                    Entry::Announced {
+                        date,
+                        name: name.to_string(),
+                    }
+                } else {
+                    Entry::Cancelled(date)
+                }
+            }
+            _ => return Err(err("expected solar, lunar, date or cancel")),
+        };
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_bundled_holidays() {
+        let holidays = holidays_in(1403).unwrap();
+        let nowruz: Vec<_> = holidays
+            .iter()
+            .filter(|holiday| holiday.name() == "Nowruz")
+            .map(Holiday::date)
+            .collect();
+        assert_eq!(
+            nowruz,
+            (1..=4).map(|day| ymd(1403, 1, day)).collect::<Vec<_>>()
+        );
+        assert!(
+            holidays
+                .windows(2)
+                .all(|pair| pair[0].date() <= pair[1].date())
+        );
+
+        assert!(is_holiday(ymd(1403, 11, 22)));
+        assert!(!is_holiday(ymd(1403, 11, 23)));
+        // 10 Muharram 1446.
+        let ashura = HolidayCalendar::iran().holidays_on(ymd(1403, 4, 27));
+        assert_eq!(ashura.len(), 1);
+        assert_eq!(ashura[0].name(), "Ashura");
+        assert_eq!(ashura[0].kind(), HolidayKind::Lunar);
+    }
+
+    #[test]
+    fn test_lunar_holiday_twice_in_a_year() {
+        // Lunar years are about 11 days shorter, so 21 Ramadan fell both
+        // at the start and at the end of 1404.
+        let dates: Vec<_> = holidays_in(1404)
+            .unwrap()
+            .into_iter()
+            .filter(|holiday| holiday.name() == "Martyrdom of Imam Ali")
+            .map(|holiday| holiday.date())
+            .collect();
+        assert_eq!(dates, [ymd(1404, 1, 1), ymd(1404, 12, 19)]);
+    }
+
+    #[test]
+    fn test_overrides() {
+        // Officially observed a day earlier than the tabular dates.
+        let calendar = HolidayCalendar::iran()
+            .with_overrides(
+                "cancel 1403/04/26\ncancel 1403/04/27\n\
+                 date 1403/04/25 Tasua\ndate 1403/04/26 Ashura\n",
+            )
+            .unwrap();
+        assert!(!calendar.is_holiday(ymd(1403, 4, 27)));
+        let ashura = calendar.holidays_on(ymd(1403, 4, 26));
+        assert_eq!(ashura.len(), 1);
+        assert_eq!(ashura[0].name(), "Ashura");
+        assert_eq!(ashura[0].kind(), HolidayKind::Announced);
+
+        let observed = HijriTable::parse("1446/01 1403/04/17\n1446/02 1403/05/16\n").unwrap();
+        let calendar = HolidayCalendar::iran().with_hijri_table(observed);
+        assert_eq!(calendar.holidays_on(ymd(1403, 4, 26))[0].name(), "Ashura");
+        assert_eq!(
+            calendar.holidays_on(ymd(1403, 4, 26))[0].kind(),
+            HolidayKind::Lunar
+        );
+
+        let custom = HolidayCalendar::parse("# test\n\nsolar 12/30 Leap day\n").unwrap();
+        assert_eq!(custom.holidays_in(1403).unwrap().len(), 1);
+        assert!(custom.holidays_in(1404).unwrap().is_empty());
+
+        assert_eq!(
+            HolidayCalendar::parse("solar 01/01 Nowruz\nlunar 13/01 Nope").unwrap_err(),
+            JalaliError::HolidayData {
+                line: 2,
+                reason: "month should be 1-12".to_string()
+            }
+        );
+        assert!(HolidayCalendar::parse("weekly 01/01 Friday").is_err());
+        assert!(HolidayCalendar::parse("solar 01/01").is_err());
+    }
+
+    #[test]
+    fn test_holidays_on_matches_holidays_in() {
+        let calendar = HolidayCalendar::iran()
+            .with_overrides("cancel 1404/01/01\ndate 1404/02/05 Announced\n")
+            .unwrap();
+        for year in [1403, 1404] {
+            let listed = calendar.holidays_in(year).unwrap();
+            let mut date = ymd(year, 1, 1);
+            while date.year() == year {
+                let expected: Vec<_> = listed
+                    .iter()
+                    .filter(|holiday| holiday.date() == date)
+                    .cloned()
+                    .collect();
+                assert_eq!(calendar.holidays_on(date), expected, "{}", date);
+                assert_eq!(calendar.is_holiday(date), !expected.is_empty());
+                date = date.checked_add_days(1).unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Day numbers.
+//!
+//! Every calendar in this crate converts through the Julian Day Number
+//! (JDN), the count of days since 1 January 4713 BC (Julian) with the day
+//! taken from noon to noon.  The other day counts here are whole days
+//! offset from it:
+//!
+//! - Modified Julian Day (MJD): days since midnight of 17 November 1858,
+//!   `JDN - 2_400_001`.
+//! - Rata Die (RD): days with 1 January 1 (proleptic Gregorian) as day 1,
+//!   `JDN - 1_721_425`.
+//! - Unix day: days since 1 January 1970, `JDN - 2_440_588`; a Unix
+//!   timestamp divided (flooring) by 86400.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    GREGORIAN_CE_JDN_OFFSET, JalaliDate, JalaliError, LeapRule, MJD_EPOCH_JDN, RATA_DIE_EPOCH_JDN,
+    UNIX_EPOCH_JDN, check_jalali_date_with, jalali_to_jdn_with, jdn_to_jalali_with,
+    try_gregorian_date,
+};
+
+/// Modified Julian Day of a JDN: `jdn - 2_400_001`, so MJD 0 is
+/// 1858-11-17 (Gregorian).
+pub fn jdn_to_mjd(jdn: i64) -> i64 {
+    jdn - MJD_EPOCH_JDN
+}
+
+/// JDN of a Modified Julian Day: `mjd + 2_400_001`.
+pub fn mjd_to_jdn(mjd: i64) -> i64 {
+    mjd + MJD_EPOCH_JDN
+}
+
+/// Rata Die of a JDN: `jdn - 1_721_425`, so RD 1 is 0001-01-01
+/// (proleptic Gregorian).
+pub fn jdn_to_rata_die(jdn: i64) -> i64 {
+    jdn - RATA_DIE_EPOCH_JDN
+}
+
+/// JDN of a Rata Die day: `rata_die + 1_721_425`.
+pub fn rata_die_to_jdn(rata_die: i64) -> i64 {
+    rata_die + RATA_DIE_EPOCH_JDN
+}
+
+/// Days since 1970-01-01 of a JDN: `jdn - 2_440_588`. A Unix timestamp
+/// divided by 86400 (rounding down) is a Unix day.
+pub fn jdn_to_unix_day(jdn: i64) -> i64 {
+    jdn - UNIX_EPOCH_JDN
+}
+
+/// JDN of a Unix day: `unix_day + 2_440_588`.
+pub fn unix_day_to_jdn(unix_day: i64) -> i64 {
+    unix_day + UNIX_EPOCH_JDN
+}
+
+/// JDN of a Jalali date; fails with `InvalidMonth`/`InvalidDay` for
+/// invalid dates and `OutOfRange` outside years 1 to `MAX_JALALI_YEAR`.
+pub fn try_jalali_to_jdn(jy: i32, jm: u8, jd: u8) -> Result<i64, JalaliError> {
+    Ok(JalaliDate::try_from_ymd(jy, jm, jd)?.to_jdn())
+}
+
+/// `try_jalali_to_jdn` under `rule`, within the rule's year range.
+pub fn try_jalali_to_jdn_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<i64, JalaliError> {
+    check_jalali_date_with(rule, jy, jm, jd)?;
+    Ok(jalali_to_jdn_with(rule, jy, jm, jd))
+}
+
+/// JDN of a Jalali date; panics where `try_jalali_to_jdn` fails.
+pub fn jalali_to_jdn(jy: i32, jm: u8, jd: u8) -> i64 {
+    try_jalali_to_jdn(jy, jm, jd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+/// Jalali year, month and day of a JDN; `OutOfRange` before 1 Farvardin
+/// of year 1 or after the last day of `MAX_JALALI_YEAR`.
+pub fn try_jdn_to_jalali(jdn: i64) -> Result<(i32, u8, u8), JalaliError> {
+    let date = JalaliDate::try_from_jdn(jdn)?;
+    Ok((date.year(), date.month(), date.day()))
+}
+
+/// `try_jdn_to_jalali` under `rule`, within the rule's year range.
+pub fn try_jdn_to_jalali_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jdn: i64,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let years = rule.year_range();
+    if jdn < rule.new_year_jdn(*years.start()) || jdn >= rule.new_year_jdn(years.end() + 1) {
+        return Err(JalaliError::OutOfRange);
+    }
+    Ok(jdn_to_jalali_with(rule, jdn))
+}
+
+/// Jalali date of a JDN; panics where `try_jdn_to_jalali` fails.
+pub fn jdn_to_jalali(jdn: i64) -> (i32, u8, u8) {
+    try_jdn_to_jalali(jdn).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+/// JDN of a proleptic Gregorian date; `InvalidMonth`/`InvalidDay` for
+/// dates that do not exist and `OutOfRange` for years outside chrono's
+/// `NaiveDate` range.
+pub fn try_gregorian_to_jdn(gy: i32, gm: u32, gd: u32) -> Result<i64, JalaliError> {
+    let date = try_gregorian_date(gy, gm, gd)?;
+    Ok(date.num_days_from_ce() as i64 + GREGORIAN_CE_JDN_OFFSET)
+}
+
+/// JDN of a proleptic Gregorian date; panics where `try_gregorian_to_jdn`
+/// fails.
+pub fn gregorian_to_jdn(gy: i32, gm: u32, gd: u32) -> i64 {
+    try_gregorian_to_jdn(gy, gm, gd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+/// Proleptic Gregorian date of a JDN; `OutOfRange` outside chrono's
+/// `NaiveDate` range.
+pub fn try_jdn_to_gregorian(jdn: i64) -> Result<(i32, u32, u32), JalaliError> {
+    let date = i32::try_from(jdn - GREGORIAN_CE_JDN_OFFSET)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or(JalaliError::OutOfRange)?;
+    Ok((date.year(), date.month(), date.day()))
+}
+
+/// Proleptic Gregorian date of a JDN; panics where `try_jdn_to_gregorian`
+/// fails.
+pub fn jdn_to_gregorian(jdn: i64) -> (i32, u32, u32) {
+    try_jdn_to_gregorian(jdn).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Birashk2820Rule, JALALI_EPOCH_JDN, MIN_JALALI_YEAR};
+
+    #[test]
+    fn test_day_numbers_of_j2000() {
+        // 1 January 2000 = 11 Dey 1378.
+        let jdn = gregorian_to_jdn(2000, 1, 1);
+        assert_eq!(jdn, 2_451_545);
+        assert_eq!(jalali_to_jdn(1378, 10, 11), jdn);
+        assert_eq!(jdn_to_jalali(jdn), (1378, 10, 11));
+        assert_eq!(jdn_to_gregorian(jdn), (2000, 1, 1));
+
+        assert_eq!(jdn_to_mjd(jdn), 51_544);
+        assert_eq!(jdn_to_rata_die(jdn), 730_120);
+        assert_eq!(jdn_to_unix_day(jdn), 10_957);
+        assert_eq!(mjd_to_jdn(51_544), jdn);
+        assert_eq!(rata_die_to_jdn(730_120), jdn);
+        assert_eq!(unix_day_to_jdn(10_957), jdn);
+        assert_eq!(jdn_to_rata_die(gregorian_to_jdn(1, 1, 1)), 1);
+    }
+
+    #[test]
+    fn test_day_number_errors_and_limits() {
+        assert_eq!(jdn_to_gregorian(0), (-4713, 11, 24));
+        assert_eq!(
+            try_gregorian_to_jdn(2023, 2, 29),
+            Err(JalaliError::InvalidDay {
+                year: 2023,
+                month: 2,
+                day: 29
+            })
+        );
+        assert_eq!(
+            try_gregorian_to_jdn(2023, 13, 1),
+            Err(JalaliError::InvalidMonth { month: 13 })
+        );
+        assert_eq!(
+            try_gregorian_to_jdn(-300_000, 1, 1),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(try_jdn_to_gregorian(i64::MAX), Err(JalaliError::OutOfRange));
+
+        assert_eq!(try_jdn_to_jalali(0), Err(JalaliError::OutOfRange));
+        assert_eq!(try_jdn_to_jalali(i64::MIN), Err(JalaliError::OutOfRange));
+        assert_eq!(
+            try_jdn_to_jalali(JALALI_EPOCH_JDN),
+            Ok((MIN_JALALI_YEAR, 1, 1))
+        );
+        assert_eq!(
+            try_jdn_to_jalali(JALALI_EPOCH_JDN - 1),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(
+            try_jalali_to_jdn(1404, 12, 30),
+            Err(JalaliError::InvalidDay {
+                year: 1404,
+                month: 12,
+                day: 30
+            })
+        );
+
+        // Birashk makes 1404 the leap year instead of 1403, so its 1404
+        // starts and ends a day earlier.
+        let jdn = try_jalali_to_jdn_with(&Birashk2820Rule, 1404, 12, 30).unwrap();
+        assert_eq!(
+            try_jdn_to_jalali_with(&Birashk2820Rule, jdn),
+            Ok((1404, 12, 30))
+        );
+        assert_eq!(jdn_to_jalali(jdn), (1404, 12, 29));
+        assert_eq!(
+            try_jdn_to_jalali_with(&Birashk2820Rule, i64::MIN),
+            Err(JalaliError::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Leap-year rules for the Jalali calendar.
+//!
+//! A rule only has to say on which Julian Day Number each year starts; the
+//! month layout (6 x 31, 5 x 30, Esfand 29/30) is the same for all of them.
+//!
+//! The `_with` conversions accept years `MIN_JALALI_YEAR..=MAX_JALALI_YEAR`
+//! by default.  Wrapping a rule in `Proleptic` extends that back to
+//! `MIN_PROLEPTIC_JALALI_YEAR` with astronomical year numbering: year 0 is
+//! the year before 1 and -1 the year before that.  `AstronomicalRule` starts
+//! a few years later, as its equinox model drifts that far back.
+
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::astro::{MEAN_TROPICAL_YEAR, equation_of_time, march_equinox_ut};
+use crate::{
+    DAYS_IN_LEAP_YEAR, GREGORIAN_CE_JDN_OFFSET, JALALI_EPOCH_JDN, MAX_JALALI_YEAR, MIN_JALALI_YEAR,
+    MIN_PROLEPTIC_JALALI_YEAR, days_before_year_internal, is_leap,
+};
+
+// Gregorian year in which Jalali year 0 ends; Nowruz of `jy` falls in `jy + 621`.
+pub(crate) const JALALI_TO_GREGORIAN_YEAR_OFFSET: i32 = 621;
+
+const BIRASHK_CYCLE_YEARS: i64 = 2820;
+const BIRASHK_CYCLE_DAYS: i64 = 1_029_983;
+// The 2820-year grand cycle is anchored on year 475.
+const BIRASHK_CYCLE_BASE_YEAR: i64 = 474;
+
+// Tehran (51°25' E); the astronomical rule compares the equinox with local
+// apparent noon there.
+pub(crate) const TEHRAN_LONGITUDE_DEGREES: f64 = 51.0 + 25.0 / 60.0;
+
+pub trait LeapRule {
+    /// Julian Day Number of 1 Farvardin of `jy`.
+    fn new_year_jdn(&self, jy: i32) -> i64;
+
+    fn is_leap(&self, jy: i32) -> bool {
+        self.new_year_jdn(jy + 1) - self.new_year_jdn(jy) == DAYS_IN_LEAP_YEAR
+    }
+
+    /// The Jalali year that contains `jdn`.
+    fn year_of_jdn(&self, jdn: i64) -> i32 {
+        // Start from the mean-year estimate, which is at most a year off.
+        let mut year = ((jdn - JALALI_EPOCH_JDN) as f64 / MEAN_TROPICAL_YEAR).floor() as i32 + 1;
+        while self.new_year_jdn(year) > jdn {
+            year -= 1;
+        }
+        while self.new_year_jdn(year + 1) <= jdn {
+            year += 1;
+        }
+        year
+    }
+
+    /// Years the `_with` conversions accept under this rule.
+    fn year_range(&self) -> RangeInclusive<i32> {
+        MIN_JALALI_YEAR..=MAX_JALALI_YEAR
+    }
+}
+
+/// The 33-year arithmetic rule (`LEAP_CYCLE` / `LEAP_REMAINDERS`) used by
+/// every function in this crate unless another rule is passed in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ThirtyThreeYearRule;
+
+impl LeapRule for ThirtyThreeYearRule {
+    fn new_year_jdn(&self, jy: i32) -> i64 {
+        JALALI_EPOCH_JDN + days_before_year_internal(jy)
+    }
+
+    fn is_leap(&self, jy: i32) -> bool {
+        is_leap(jy)
+    }
+
+    fn year_of_jdn(&self, jdn: i64) -> i32 {
+        crate::jalali_year_of_jdn_internal(jdn)
+    }
+}
+
+/// Ahmad Birashk's 2820-year grand cycle (683 leap years per cycle).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Birashk2820Rule;
+
+impl Birashk2820Rule {
+    // Splits a year into (grand cycles since year 475, year in 474..3294).
+    fn cycle_position(jy: i32) -> (i64, i64) {
+        let y = jy as i64 - BIRASHK_CYCLE_BASE_YEAR;
+        (
+            y.div_euclid(BIRASHK_CYCLE_YEARS),
+            y.rem_euclid(BIRASHK_CYCLE_YEARS) + BIRASHK_CYCLE_BASE_YEAR,
+        )
+    }
+}
+
+impl LeapRule for Birashk2820Rule {
+    fn new_year_jdn(&self, jy: i32) -> i64 {
+        let (cycles, year_in_cycle) = Birashk2820Rule::cycle_position(jy);
+        JALALI_EPOCH_JDN
+            + BIRASHK_CYCLE_DAYS * cycles
+            + 365 * (year_in_cycle - 1)
+            + (31 * year_in_cycle - 5).div_euclid(128)
+    }
+
+    fn is_leap(&self, jy: i32) -> bool {
+        let (_, year_in_cycle) = Birashk2820Rule::cycle_position(jy);
+        ((year_in_cycle + 38) * 31).rem_euclid(128) < 31
+    }
+
+    fn year_of_jdn(&self, jdn: i64) -> i32 {
+        let days = jdn - self.new_year_jdn(BIRASHK_CYCLE_BASE_YEAR as i32 + 1);
+        let cycles = days.div_euclid(BIRASHK_CYCLE_DAYS);
+        let day_in_cycle = days.rem_euclid(BIRASHK_CYCLE_DAYS);
+        let year_in_cycle = if day_in_cycle == BIRASHK_CYCLE_DAYS - 1 {
+            BIRASHK_CYCLE_YEARS
+        } else {
+            (128 * day_in_cycle + 46_878).div_euclid(46_751)
+        };
+        (BIRASHK_CYCLE_BASE_YEAR + BIRASHK_CYCLE_YEARS * cycles + year_in_cycle) as i32
+    }
+}
+
+/// Official Iranian reckoning: the year starts on the day of the March
+/// equinox if it happens before apparent noon in Tehran, otherwise on the
+/// next day. Accuracy follows the equinox model (a few minutes for
+/// Gregorian years -1000..3000), so years whose equinox falls within
+/// minutes of noon may differ from printed calendars.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AstronomicalRule;
+
+impl LeapRule for AstronomicalRule {
+    fn new_year_jdn(&self, jy: i32) -> i64 {
+        let equinox = march_equinox_ut(jy + JALALI_TO_GREGORIAN_YEAR_OFFSET);
+        // Shift to Tehran local mean time; Julian Dates start at noon, so
+        // +0.5 puts midnight at the integer boundary.
+        let local = equinox + TEHRAN_LONGITUDE_DEGREES / 360.0 + 0.5;
+        let local_day = local.floor();
+        let apparent_noon = 0.5 - equation_of_time(equinox);
+        if local - local_day < apparent_noon {
+            local_day as i64
+        } else {
+            local_day as i64 + 1
+        }
+    }
+}
+
+/// A rule extended to proleptic years, 0 and before, down to
+/// `MIN_PROLEPTIC_JALALI_YEAR` (a few years later for `AstronomicalRule`,
+/// see `year_range`).  The 33-year rule simply repeats its cycle
+/// backwards; conversions round-trip over the whole range.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Proleptic<R = ThirtyThreeYearRule>(pub R);
+
+impl<R: LeapRule> LeapRule for Proleptic<R> {
+    fn new_year_jdn(&self, jy: i32) -> i64 {
+        self.0.new_year_jdn(jy)
+    }
+
+    fn is_leap(&self, jy: i32) -> bool {
+        self.0.is_leap(jy)
+    }
+
+    fn year_of_jdn(&self, jdn: i64) -> i32 {
+        self.0.year_of_jdn(jdn)
+    }
+
+    /// From the first year that starts inside chrono's range under the
+    /// wrapped rule: `MIN_PROLEPTIC_JALALI_YEAR` for the arithmetic rules.
+    fn year_range(&self) -> RangeInclusive<i32> {
+        let min_jdn = NaiveDate::MIN.num_days_from_ce() as i64 + GREGORIAN_CE_JDN_OFFSET;
+        let mut first = MIN_PROLEPTIC_JALALI_YEAR;
+        while self.0.new_year_jdn(first) < min_jdn {
+            first += 1;
+        }
+        first..=MAX_JALALI_YEAR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GREGORIAN_CE_JDN_OFFSET, JalaliError, LEAP_CYCLE, try_gregorian_to_jalali,
+        try_gregorian_to_jalali_with, try_jalali_to_gregorian_with, try_jdn_to_jalali_with,
+    };
+    use chrono::{Datelike, NaiveDate};
+
+    fn jdn_of(year: i32, month: u32, day: u32) -> i64 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .num_days_from_ce() as i64
+            + GREGORIAN_CE_JDN_OFFSET
+    }
+
+    #[test]
+    fn test_thirty_three_year_rule_matches_is_leap() {
+        let rule = ThirtyThreeYearRule;
+        for jy in 1..(10 * LEAP_CYCLE + 1400) {
+            assert_eq!(
+                rule.new_year_jdn(jy + 1) - rule.new_year_jdn(jy) == DAYS_IN_LEAP_YEAR,
+                is_leap(jy),
+                "year {}",
+                jy
+            );
+        }
+        assert_eq!(rule.new_year_jdn(1403), jdn_of(2024, 3, 20));
+    }
+
+    #[test]
+    fn test_birashk_rule() {
+        let rule = Birashk2820Rule;
+        assert_eq!(rule.new_year_jdn(1), JALALI_EPOCH_JDN);
+        // Birashk puts the leap year at 1404 where the official calendar has 1403.
+        assert!(!rule.is_leap(1403));
+        assert!(rule.is_leap(1404));
+        assert!(rule.is_leap(1399));
+        for jy in -3000..6000 {
+            let start = rule.new_year_jdn(jy);
+            let length = rule.new_year_jdn(jy + 1) - start;
+            assert_eq!(length == DAYS_IN_LEAP_YEAR, rule.is_leap(jy), "year {}", jy);
+            assert_eq!(rule.year_of_jdn(start), jy);
+            assert_eq!(rule.year_of_jdn(start - 1), jy - 1);
+        }
+    }
+
+    #[test]
+    fn test_astronomical_rule_known_nowruz_dates() {
+        let rule = AstronomicalRule;
+        // Equinox at 06:36 Tehran time: same day.
+        assert_eq!(rule.new_year_jdn(1403), jdn_of(2024, 3, 20));
+        // Equinox at 12:31 Tehran time: after noon, so the next day.
+        assert_eq!(rule.new_year_jdn(1404), jdn_of(2025, 3, 21));
+        assert_eq!(rule.new_year_jdn(1400), jdn_of(2021, 3, 21));
+        assert!(rule.is_leap(1399));
+        assert!(rule.is_leap(1403));
+        assert!(!rule.is_leap(1404));
+        let start = rule.new_year_jdn(1350);
+        assert_eq!(rule.year_of_jdn(start), 1350);
+        assert_eq!(rule.year_of_jdn(start - 1), 1349);
+    }
+
+    #[test]
+    fn test_proleptic_years() {
+        let rule = Proleptic(ThirtyThreeYearRule);
+        assert!(!is_leap(0));
+        assert!(is_leap(-3));
+        assert!(is_leap(-28));
+        // The day before 1 Farvardin 1 is 29 Esfand 0.
+        assert_eq!(
+            try_gregorian_to_jalali_with(&rule, 622, 3, 21),
+            Ok((0, 12, 29))
+        );
+        assert_eq!(
+            try_gregorian_to_jalali(622, 3, 21),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(
+            try_jalali_to_gregorian_with(&rule, -3, 12, 30),
+            Ok((619, 3, 22))
+        );
+
+        for (gy, gm, gd) in [(1, 1, 1), (0, 2, 29), (-500, 6, 15), (-4713, 11, 24)] {
+            let (jy, jm, jd) = try_gregorian_to_jalali_with(&rule, gy, gm, gd).unwrap();
+            assert_eq!(
+                try_jalali_to_gregorian_with(&rule, jy, jm, jd),
+                Ok((gy, gm, gd))
+            );
+        }
+        for jy in (-40_000..0).step_by(37) {
+            let start = rule.new_year_jdn(jy);
+            assert_eq!(rule.year_of_jdn(start), jy, "year {}", jy);
+            assert_eq!(rule.year_of_jdn(start - 1), jy - 1, "year {}", jy);
+        }
+
+        // The lower bound is the first year that starts in chrono's range.
+        let first = MIN_PROLEPTIC_JALALI_YEAR;
+        assert!(try_jalali_to_gregorian_with(&rule, first, 1, 1).is_ok());
+        assert!(
+            NaiveDate::from_num_days_from_ce_opt(
+                (rule.new_year_jdn(first - 1) - GREGORIAN_CE_JDN_OFFSET) as i32
+            )
+            .is_none()
+        );
+        assert_eq!(
+            try_jalali_to_gregorian_with(&rule, first - 1, 12, 29),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(rule.year_range(), first..=MAX_JALALI_YEAR);
+
+        // The astronomical model's first year starts a little later.
+        let astronomical = Proleptic(AstronomicalRule);
+        let first = *astronomical.year_range().start();
+        assert!(first > MIN_PROLEPTIC_JALALI_YEAR);
+        assert!(try_jalali_to_gregorian_with(&astronomical, first, 1, 1).is_ok());
+        assert_eq!(
+            try_jalali_to_gregorian_with(&astronomical, first - 1, 1, 1),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(
+            try_jdn_to_jalali_with(&Proleptic(Birashk2820Rule), 0),
+            try_gregorian_to_jalali_with(&Proleptic(Birashk2820Rule), -4713, 11, 24)
+        );
+    }
+}
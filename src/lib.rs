@@ -5,6 +5,63 @@
 
 use chrono::{Datelike, NaiveDate};
 
+mod astro;
+mod business;
+mod date;
+mod datetime;
+mod era;
+mod error;
+mod format;
+mod grid;
+mod hijri;
+mod holiday;
+mod jdn;
+mod leap;
+mod locale;
+mod normalize;
+mod parse;
+mod period;
+mod range;
+#[cfg(feature = "serde")]
+pub mod serde_formats;
+mod week;
+mod words;
+mod zoroastrian;
+
+pub use astro::{vernal_equinox, vernal_equinox_utc};
+pub use business::{BusinessCalendar, WorkDay, Workweek};
+pub use date::{JalaliDate, MonthEndPolicy};
+pub use datetime::{DstPolicy, JalaliDateTime};
+pub use era::Era;
+pub use error::JalaliError;
+pub use format::JalaliFormat;
+pub use grid::{DayCell, GridWeek, MonthGrid, YearGrid};
+pub use hijri::{
+    HijriRule, HijriTable, TabularHijriRule, gregorian_to_hijri, hijri_to_gregorian,
+    hijri_to_jalali, jalali_to_hijri, try_gregorian_to_hijri, try_gregorian_to_hijri_with,
+    try_hijri_to_gregorian, try_hijri_to_gregorian_with, try_hijri_to_jalali,
+    try_hijri_to_jalali_with, try_jalali_to_hijri, try_jalali_to_hijri_with,
+};
+pub use holiday::{Holiday, HolidayCalendar, HolidayKind, holidays_in, is_holiday};
+pub use jdn::{
+    gregorian_to_jdn, jalali_to_jdn, jdn_to_gregorian, jdn_to_jalali, jdn_to_mjd, jdn_to_rata_die,
+    jdn_to_unix_day, mjd_to_jdn, rata_die_to_jdn, try_gregorian_to_jdn, try_jalali_to_jdn,
+    try_jalali_to_jdn_with, try_jdn_to_gregorian, try_jdn_to_jalali, try_jdn_to_jalali_with,
+    unix_day_to_jdn,
+};
+pub use leap::{AstronomicalRule, Birashk2820Rule, LeapRule, Proleptic, ThirtyThreeYearRule};
+pub use locale::Locale;
+pub use normalize::{normalize_persian, parse_number, to_latin_numerals};
+pub use parse::{COMMON_JALALI_PATTERNS, JalaliParsed};
+pub use period::JalaliPeriod;
+pub use range::{JalaliDateRange, RangeStep};
+pub use week::{FirstWeek, JalaliWeek, WeekRule};
+pub use words::{english_cardinal, english_ordinal, persian_cardinal, persian_ordinal};
+pub use zoroastrian::{
+    GATHA_MONTH, ZoroastrianCalendar, ZoroastrianDate, ZoroastrianFormat, jalali_to_zoroastrian,
+    try_jalali_to_zoroastrian, try_zoroastrian_to_jalali, zoroastrian_to_jalali,
+};
+
 pub const DAYS_IN_LONG_MONTH: u8 = 31;
 pub const DAYS_IN_MID_MONTH: u8 = 30;
 pub const DAYS_IN_SHORT_MONTH: u8 = 29;
@@ -19,11 +76,22 @@ pub const DAYS_IN_LEAP_YEAR: i64 = 366;
 
 pub const GREGORIAN_CE_JDN_OFFSET: i64 = 1_721_425;
 pub const UNIX_EPOCH_JDN: i64 = 2_440_588;
+pub const MJD_EPOCH_JDN: i64 = 2_400_001; // MJD 0 = 1858-11-17 Gregorian
+pub const RATA_DIE_EPOCH_JDN: i64 = GREGORIAN_CE_JDN_OFFSET; // RD 1 = 0001-01-01 Gregorian
 pub const JALALI_YEAR_AT_UNIX_EPOCH: i32 = 1_348;
 pub const JALALI_YDAY_AT_UNIX_EPOCH: i32 = 286;
 
 pub const LEAP_CYCLE: i32 = 33;
 pub const LEAP_REMAINDERS: [i32; 8] = [1, 5, 9, 13, 17, 22, 26, 30];
+pub const DAYS_IN_LEAP_CYCLE: i64 =
+    DAYS_IN_COMMON_YEAR * LEAP_CYCLE as i64 + LEAP_REMAINDERS.len() as i64;
+
+// Years 1-5 follow their own rule in `is_leap` (only year 4 is leap); the
+// 33-year table applies from year 6 on.
+pub const FIRST_CYCLE_RULE_YEAR: i32 = 6;
+pub const LEAP_YEARS_BEFORE_CYCLE_RULE: i64 = 1;
+
+pub const JALALI_EPOCH_JDN: i64 = 1_948_321; // 1 Farvardin 1 = 622-03-22 Gregorian
 
 pub const JDN_MONTH_CORRECTION: i64 = 14;
 pub const MONTHS_PER_YEAR: i64 = 12;
@@ -37,6 +105,14 @@ pub const MIN_GREGORIAN_YEAR_FOR_JALALI: i32 = 622;
 pub const MIN_GREGORIAN_MONTH_FOR_JALALI: u32 = 3;
 pub const MIN_GREGORIAN_DAY_FOR_JALALI: u32 = 22;
 
+// Range accepted by `JalaliDate`; the upper bound is the last Jalali year
+// that ends inside chrono's `NaiveDate` range.
+pub const MIN_JALALI_YEAR: i32 = 1;
+// Lower bound with `Proleptic` rules: the first Jalali year that starts
+// inside chrono's `NaiveDate` range.
+pub const MIN_PROLEPTIC_JALALI_YEAR: i32 = -262_764;
+pub const MAX_JALALI_YEAR: i32 = 261_520;
+
 pub const MONTH_NAMES: [&str; 12] = [
     "Farvardin",
     "Ordibehesht",
@@ -75,6 +151,30 @@ pub const ENGLISH_WEEK_DAYS_AB: [&str; 7] = ["Sa", "Su", "Mo", "Tu", "We", "Th",
 
 pub const JALALI_WEEKDAYS_ABBR_ALT: [&str; 7] = ["Sha", "Yek", "Dos", "Ses", "Cha", "Pan", "Jom"];
 
+pub const JALALI_WEEKDAYS: [&str; 7] = [
+    "Shanbeh",
+    "Yekshanbeh",
+    "Doshanbeh",
+    "Seshanbeh",
+    "Chaharshanbeh",
+    "Panjshanbeh",
+    "Jomeh",
+];
+
+pub const PERSIAN_WEEK_DAYS: [&str; 7] = [
+    "شنبه",
+    "یکشنبه",
+    "دوشنبه",
+    "سه‌شنبه",
+    "چهارشنبه",
+    "پنجشنبه",
+    "جمعه",
+];
+
+pub const MONTH_NAMES_ABBR: [&str; 12] = [
+    "Far", "Ord", "Kho", "Tir", "Mor", "Sha", "Meh", "Aba", "Aza", "Dey", "Bah", "Esf",
+];
+
 pub const JALALI_FRIDAY_INDEX: usize = 6; // Friday is the 7th day, 0-indexed from Saturday
 
 pub const MONTHS_PER_YEAR_COUNT: u8 = 12;
@@ -115,100 +215,178 @@ pub fn to_persian_numerals(input: &str) -> String {
         .collect()
 }
 
+pub fn try_jalali_day_of_year(jy: i32, jm: u8, jd: u8) -> Result<i32, JalaliError> {
+    JalaliDate::try_from_ymd(jy, jm, jd).map(|date| date.ordinal() as i32)
+}
+
 pub fn jalali_day_of_year(jy: i32, jm: u8, jd: u8) -> i32 {
-    let mut day_of_year: i32 = 0;
-    for m_iter in 1..jm {
-        day_of_year += days_in_month(jy, m_iter) as i32;
-    }
-    day_of_year += jd as i32;
-    day_of_year
+    try_jalali_day_of_year(jy, jm, jd).unwrap_or_else(|e| panic!("Error: {}.", e))
 }
 
 // Chekc the Jalali year is leap or not
 pub fn is_leap(jy: i32) -> bool {
-    if jy <= 5 {
-        if jy <= 0 {
-            return false;
-        }
+    if (1..FIRST_CYCLE_RULE_YEAR).contains(&jy) {
         return jy % 4 == 0;
     }
-    let md = jy % LEAP_CYCLE;
-    LEAP_REMAINDERS.contains(&md)
+    // Proleptic years (0 and before) continue the 33-year table backwards.
+    LEAP_REMAINDERS.contains(&jy.rem_euclid(LEAP_CYCLE))
 }
 
-pub fn days_in_month(jy: i32, jm: u8) -> u8 {
+pub fn try_days_in_month(jy: i32, jm: u8) -> Result<u8, JalaliError> {
+    try_days_in_month_with(&ThirtyThreeYearRule, jy, jm)
+}
+
+pub fn try_days_in_month_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+) -> Result<u8, JalaliError> {
     match jm {
-        1..=MONTHS_WITH_LONG_DAYS_END => DAYS_IN_LONG_MONTH,
-        7..=MONTHS_WITH_MID_DAYS_END => DAYS_IN_MID_MONTH,
+        1..=MONTHS_WITH_LONG_DAYS_END => Ok(DAYS_IN_LONG_MONTH),
+        7..=MONTHS_WITH_MID_DAYS_END => Ok(DAYS_IN_MID_MONTH),
         LAST_MONTH_INDEX => {
-            if is_leap(jy) {
-                DAYS_IN_MID_MONTH
+            if rule.is_leap(jy) {
+                Ok(DAYS_IN_MID_MONTH)
             } else {
-                DAYS_IN_SHORT_MONTH
+                Ok(DAYS_IN_SHORT_MONTH)
             }
         }
-        _ => panic!(
-            "Error: Month out of range (1-12)."
-        ),
+        _ => Err(JalaliError::InvalidMonth { month: jm as u32 }),
     }
 }
 
-fn jalali_to_jdn_internal(jy: i32, jm: u8, jd: u8) -> i64 {
-    let mut input_yday_0_indexed: i32 = 0;
-    for m_iter in 1..jm {
-        input_yday_0_indexed += days_in_month(jy, m_iter) as i32;
-    }
-    input_yday_0_indexed += jd as i32 - 1;
+pub fn days_in_month(jy: i32, jm: u8) -> u8 {
+    try_days_in_month(jy, jm).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
 
-    let mut p_days_from_unix_epoch: i64 = 0;
+// Number of `LEAP_REMAINDERS` strictly below `remainder` (0..=LEAP_CYCLE).
+fn leap_remainders_below(remainder: i64) -> i64 {
+    LEAP_REMAINDERS
+        .iter()
+        .filter(|&&r| (r as i64) < remainder)
+        .count() as i64
+}
 
-    if jy >= JALALI_YEAR_AT_UNIX_EPOCH {
-        for i_y in JALALI_YEAR_AT_UNIX_EPOCH..jy {
-            p_days_from_unix_epoch += if is_leap(i_y) {
-                DAYS_IN_LEAP_YEAR
-            } else {
-                DAYS_IN_COMMON_YEAR
-            };
-        }
-        p_days_from_unix_epoch += input_yday_0_indexed as i64 - JALALI_YDAY_AT_UNIX_EPOCH as i64;
+// Leap years in 0..jy as counted by the bare 33-year table, without the
+// special case `is_leap` applies to years 1-5; negative (minus the leap
+// years in jy..0) for jy < 0.
+fn cycle_leap_years_before(jy: i64) -> i64 {
+    let cycle = LEAP_CYCLE as i64;
+    jy.div_euclid(cycle) * LEAP_REMAINDERS.len() as i64
+        + leap_remainders_below(jy.rem_euclid(cycle))
+}
+
+// Days from 1 Farvardin of year 1 to 1 Farvardin of `jy`, in O(1).
+pub(crate) fn days_before_year_internal(jy: i32) -> i64 {
+    let y = jy as i64;
+    if jy <= 1 {
+        // Proleptic years follow the bare 33-year table.
+        return DAYS_IN_COMMON_YEAR * (y - 1) + cycle_leap_years_before(y);
+    }
+    if jy < FIRST_CYCLE_RULE_YEAR {
+        // Only year 4 is leap before the 33-year rule takes over.
+        return DAYS_IN_COMMON_YEAR * (y - 1) + (y - 1) / 4;
+    }
+    let leap_years = cycle_leap_years_before(y)
+        - cycle_leap_years_before(FIRST_CYCLE_RULE_YEAR as i64)
+        + LEAP_YEARS_BEFORE_CYCLE_RULE;
+    DAYS_IN_COMMON_YEAR * (y - 1) + leap_years
+}
+
+// 0-indexed day of year for a month/day pair.
+pub(crate) fn jalali_month_day_to_yday_internal(jm: u8, jd: u8) -> i32 {
+    let month = jm as i32;
+    let long_months = MONTHS_WITH_LONG_DAYS_END as i32;
+    if jm <= MONTHS_WITH_LONG_DAYS_END {
+        (month - 1) * DAYS_IN_LONG_MONTH as i32 + jd as i32 - 1
     } else {
-        for i_y in jy..JALALI_YEAR_AT_UNIX_EPOCH {
-            p_days_from_unix_epoch -= if is_leap(i_y) {
-                DAYS_IN_LEAP_YEAR
-            } else {
-                DAYS_IN_COMMON_YEAR
-            };
-        }
-        p_days_from_unix_epoch -= JALALI_YDAY_AT_UNIX_EPOCH as i64 - input_yday_0_indexed as i64;
+        long_months * DAYS_IN_LONG_MONTH as i32
+            + (month - 1 - long_months) * DAYS_IN_MID_MONTH as i32
+            + jd as i32
+            - 1
     }
+}
 
-    p_days_from_unix_epoch + UNIX_EPOCH_JDN
+pub(crate) fn jalali_to_jdn_with<R: LeapRule + ?Sized>(rule: &R, jy: i32, jm: u8, jd: u8) -> i64 {
+    rule.new_year_jdn(jy) + jalali_month_day_to_yday_internal(jm, jd) as i64
 }
 
-pub fn jalali_to_gregorian(jy: i32, jm: u8, jd: u8) -> (i32, u32, u32) {
-    if jm == 0 || jm > LAST_MONTH_INDEX || jd == 0 || jd > days_in_month(jy, jm) {
-        panic!(
-            "Error: Invalid Jalali date: year {}, month {}, day {}.",
-            jy, jm, jd
-        );
+pub(crate) fn jalali_to_jdn_internal(jy: i32, jm: u8, jd: u8) -> i64 {
+    jalali_to_jdn_with(&ThirtyThreeYearRule, jy, jm, jd)
+}
+
+// Range and month/day validation shared by the `_with` conversions.
+pub(crate) fn check_jalali_date_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<(), JalaliError> {
+    if !rule.year_range().contains(&jy) {
+        return Err(JalaliError::OutOfRange);
+    }
+    let days = try_days_in_month_with(rule, jy, jm)?;
+    if jd == 0 || jd > days {
+        return Err(JalaliError::InvalidDay {
+            year: jy,
+            month: jm as u32,
+            day: jd as u32,
+        });
     }
+    Ok(())
+}
 
-    let jdn = jalali_to_jdn_internal(jy, jm, jd);
+pub fn try_jalali_day_of_year_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<i32, JalaliError> {
+    check_jalali_date_with(rule, jy, jm, jd)?;
+    Ok(jalali_month_day_to_yday_internal(jm, jd) + 1)
+}
 
-    let days_from_ce = jdn - GREGORIAN_CE_JDN_OFFSET;
+pub fn try_jalali_to_gregorian_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<(i32, u32, u32), JalaliError> {
+    check_jalali_date_with(rule, jy, jm, jd)?;
+    let jdn = jalali_to_jdn_with(rule, jy, jm, jd);
+    let gregorian = i32::try_from(jdn - GREGORIAN_CE_JDN_OFFSET)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or(JalaliError::OutOfRange)?;
+    Ok((gregorian.year(), gregorian.month(), gregorian.day()))
+}
 
-    match NaiveDate::from_num_days_from_ce_opt(days_from_ce as i32) {
-        Some(date) => (date.year(), date.month(), date.day()),
-        None => {
-            panic!(
-                "Error: Failed to convert JDN {} (days from CE: {}) to Gregorian date.",
-                jdn, days_from_ce
-            )
-        }
+pub fn try_jalali_to_gregorian(jy: i32, jm: u8, jd: u8) -> Result<(i32, u32, u32), JalaliError> {
+    let gregorian = JalaliDate::try_from_ymd(jy, jm, jd)?.to_gregorian();
+    Ok((gregorian.year(), gregorian.month(), gregorian.day()))
+}
+
+pub fn jalali_to_gregorian(jy: i32, jm: u8, jd: u8) -> (i32, u32, u32) {
+    try_jalali_to_gregorian(jy, jm, jd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+// A proleptic Gregorian date, with years outside chrono's `NaiveDate`
+// range reported as `OutOfRange` rather than as a bad day.
+pub(crate) fn try_gregorian_date(gy: i32, gm: u32, gd: u32) -> Result<NaiveDate, JalaliError> {
+    if gm == 0 || gm > MONTHS_PER_YEAR_COUNT as u32 {
+        return Err(JalaliError::InvalidMonth { month: gm });
+    }
+    if !(NaiveDate::MIN.year()..=NaiveDate::MAX.year()).contains(&gy) {
+        return Err(JalaliError::OutOfRange);
     }
+    NaiveDate::from_ymd_opt(gy, gm, gd).ok_or(JalaliError::InvalidDay {
+        year: gy,
+        month: gm,
+        day: gd,
+    })
 }
 
-fn compute_jdn_internal(year: i32, month: u32, day: u32) -> i64 {
+pub(crate) fn compute_jdn_internal(year: i32, month: u32, day: u32) -> i64 {
     let year_i64 = year as i64;
     let month_i64 = month as i64;
     let day_i64 = day as i64;
@@ -226,98 +404,113 @@ fn compute_jdn_internal(year: i32, month: u32, day: u32) -> i64 {
         - JDN_CONSTANT_ADJUST
 }
 
-fn jalali_yday_to_month_day_internal(year: i32, yday: i32) -> (u8, u8) {
-    let mut p_day_in_year = yday + 1;
-    let mut calculated_month_1_indexed: u8 = 0; // Will store the 1-indexed month. Default to 0 to indicate not found yet.
+pub(crate) fn jalali_yday_to_month_day_internal(yday: i32) -> (u8, u8) {
+    let long_months_days = (MONTHS_WITH_LONG_DAYS_END as i32) * DAYS_IN_LONG_MONTH as i32;
+    if yday < long_months_days {
+        let month = yday / DAYS_IN_LONG_MONTH as i32 + 1;
+        let day = yday % DAYS_IN_LONG_MONTH as i32 + 1;
+        (month as u8, day as u8)
+    } else {
+        let rest = yday - long_months_days;
+        let month = rest / DAYS_IN_MID_MONTH as i32 + MONTHS_WITH_LONG_DAYS_END as i32 + 1;
+        let day = rest % DAYS_IN_MID_MONTH as i32 + 1;
+        (month as u8, day as u8)
+    }
+}
 
-    // The range 1..LAST_MONTH_INDEX covers 1, 2, ..., 11.
-    for current_month_1_indexed_iter in 1..LAST_MONTH_INDEX {
-        let days_in_current_month = days_in_month(year, current_month_1_indexed_iter) as i32;
-        if p_day_in_year > days_in_current_month {
-            p_day_in_year -= days_in_current_month;
-        } else {
-            // Day falls into this month
-            calculated_month_1_indexed = current_month_1_indexed_iter;
-            break;
+// Jalali year containing `jdn` under the 33-year rule, in O(1).
+pub(crate) fn jalali_year_of_jdn_internal(jdn: i64) -> i32 {
+    let days = jdn - JALALI_EPOCH_JDN;
+    let first_cycle_rule_day = days_before_year_internal(FIRST_CYCLE_RULE_YEAR);
+
+    if days < 0 {
+        // Proleptic years: `days_before_year_internal` is already
+        // `365 * (y - 1) + cycle_leap_years_before(y)` there.
+        cycle_year_of_shifted_days(days + DAYS_IN_COMMON_YEAR)
+    } else if days < first_cycle_rule_day {
+        let mut year = 1;
+        while days >= days_before_year_internal(year + 1) {
+            year += 1;
         }
+        year
+    } else {
+        // Undo the offsets in `days_before_year_internal` so that
+        // `shifted = 365 * y + cycle_leap_years_before(y)` for the start of year y.
+        cycle_year_of_shifted_days(
+            days + DAYS_IN_COMMON_YEAR + cycle_leap_years_before(FIRST_CYCLE_RULE_YEAR as i64)
+                - LEAP_YEARS_BEFORE_CYCLE_RULE,
+        )
     }
+}
 
-    // calculated_month_1_indexed is still 0, day is in the last month
-    if calculated_month_1_indexed == 0 {
-        calculated_month_1_indexed = LAST_MONTH_INDEX;
+// Year y with `365 * y + cycle_leap_years_before(y) <= shifted` < the same
+// for y + 1: whole cycles first, then the year inside the cycle.
+fn cycle_year_of_shifted_days(shifted: i64) -> i32 {
+    let cycles = shifted.div_euclid(DAYS_IN_LEAP_CYCLE);
+    let in_cycle = shifted.rem_euclid(DAYS_IN_LEAP_CYCLE);
+    let mut year_in_cycle = in_cycle / DAYS_IN_COMMON_YEAR;
+    if DAYS_IN_COMMON_YEAR * year_in_cycle + leap_remainders_below(year_in_cycle) > in_cycle {
+        year_in_cycle -= 1;
     }
+    (cycles * LEAP_CYCLE as i64 + year_in_cycle) as i32
+}
 
-    (calculated_month_1_indexed, p_day_in_year as u8)
+pub(crate) fn jdn_to_jalali_with<R: LeapRule + ?Sized>(rule: &R, jdn: i64) -> (i32, u8, u8) {
+    let year = rule.year_of_jdn(jdn);
+    let yday = (jdn - rule.new_year_jdn(year)) as i32;
+    let (month, day) = jalali_yday_to_month_day_internal(yday);
+    (year, month, day)
 }
 
-fn days_offset_to_jalali_internal(days_offset_from_unix_epoch: i64) -> (i32, u8, u8) {
-    let mut p_offset = days_offset_from_unix_epoch;
-    let mut current_jalali_year = JALALI_YEAR_AT_UNIX_EPOCH;
+pub(crate) fn jdn_to_jalali_internal(jdn: i64) -> (i32, u8, u8) {
+    jdn_to_jalali_with(&ThirtyThreeYearRule, jdn)
+}
 
-    p_offset += JALALI_YDAY_AT_UNIX_EPOCH as i64;
+pub fn try_gregorian_to_jalali(gy: i32, gm: u32, gd: u32) -> Result<(i32, u8, u8), JalaliError> {
+    let gregorian = try_gregorian_date(gy, gm, gd)?;
+    let date = JalaliDate::try_from_gregorian(gregorian)?;
+    Ok((date.year(), date.month(), date.day()))
+}
 
-    if p_offset >= 0 {
-        loop {
-            let days_in_current_jalali_year = if is_leap(current_jalali_year) {
-                DAYS_IN_LEAP_YEAR
-            } else {
-                DAYS_IN_COMMON_YEAR
-            };
-            if p_offset < days_in_current_jalali_year {
-                break;
-            }
-            p_offset -= days_in_current_jalali_year;
-            current_jalali_year += 1;
-        }
-    } else {
-        loop {
-            current_jalali_year -= 1;
-            let days_in_previous_jalali_year = if is_leap(current_jalali_year) {
-                DAYS_IN_LEAP_YEAR
-            } else {
-                DAYS_IN_COMMON_YEAR
-            };
-            p_offset += days_in_previous_jalali_year;
-            if p_offset >= 0 {
-                break;
-            }
-        }
+pub fn try_gregorian_to_jalali_with<R: LeapRule + ?Sized>(
+    rule: &R,
+    gy: i32,
+    gm: u32,
+    gd: u32,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let gregorian = try_gregorian_date(gy, gm, gd)?;
+    let date = jdn_to_jalali_with(
+        rule,
+        gregorian.num_days_from_ce() as i64 + GREGORIAN_CE_JDN_OFFSET,
+    );
+    if !rule.year_range().contains(&date.0) {
+        return Err(JalaliError::OutOfRange);
     }
-
-    let final_yday = p_offset as i32;
-    let (final_month, final_day) =
-        jalali_yday_to_month_day_internal(current_jalali_year, final_yday);
-    (current_jalali_year, final_month, final_day)
+    Ok(date)
 }
 
 pub fn gregorian_to_jalali(gy: i32, gm: u32, gd: u32) -> (i32, u8, u8) {
-    if gy < MIN_GREGORIAN_YEAR_FOR_JALALI
-        || (gy == MIN_GREGORIAN_YEAR_FOR_JALALI
-            && (gm < MIN_GREGORIAN_MONTH_FOR_JALALI
-                || (gm == MIN_GREGORIAN_MONTH_FOR_JALALI && gd < MIN_GREGORIAN_DAY_FOR_JALALI)))
-    {
+    try_gregorian_to_jalali(gy, gm, gd).unwrap_or_else(|e| {
         panic!(
-            "Error: Input Gregorian date {}/{}/{} is before 622-03-22 Gregorian (approximate Jalali epoch start) and cannot be converted to Jalali.",
-            gy, gm, gd
-        );
-    }
-    let jdn = compute_jdn_internal(gy, gm, gd);
-
-    let days_offset_from_unix_epoch = jdn - UNIX_EPOCH_JDN;
-
-    days_offset_to_jalali_internal(days_offset_from_unix_epoch)
+            "Error: Input Gregorian date {}/{}/{} cannot be converted to Jalali: {}.",
+            gy, gm, gd, e
+        )
+    })
 }
 
 // Weekday column (0‑Sat … 6‑Fri) of the first day of a Jalali month.
 pub fn first_weekday(jy: i32, jm: u8) -> Option<u8> {
-    let (gy, gm, gd) = jalali_to_gregorian(jy, jm, 1);
-    match NaiveDate::from_ymd_opt(gy, gm, gd) {
-        Some(date) => {
-            let sun_based = date.weekday().num_days_from_sunday() as u8;
-            Some((sun_based + 1) % WEEK_DAYS_TOTAL)
-        }
-        None => None,
-    }
+    JalaliDate::from_ymd_opt(jy, jm, 1).map(|date| {
+        let sun_based = date.weekday().num_days_from_sunday() as u8;
+        (sun_based + 1) % WEEK_DAYS_TOTAL
+    })
+}
+
+pub fn first_weekday_with<R: LeapRule + ?Sized>(rule: &R, jy: i32, jm: u8) -> Option<u8> {
+    check_jalali_date_with(rule, jy, jm, 1).ok()?;
+    // JDN 0 was a Monday, so JDN + 2 counts from Saturday.
+    let jdn = jalali_to_jdn_with(rule, jy, jm, 1);
+    Some((jdn + 2).rem_euclid(WEEK_DAYS_TOTAL as i64) as u8)
 }
 
 #[cfg(test)]
@@ -382,6 +575,45 @@ mod tests {
         days_in_month(1399, 13);
     }
 
+    #[test]
+    fn test_try_conversions_report_errors() {
+        assert_eq!(
+            try_days_in_month(1399, 13),
+            Err(JalaliError::InvalidMonth { month: 13 })
+        );
+        assert_eq!(
+            try_jalali_to_gregorian(1398, 12, 30),
+            Err(JalaliError::InvalidDay {
+                year: 1398,
+                month: 12,
+                day: 30
+            })
+        );
+        assert_eq!(
+            try_gregorian_to_jalali(622, 3, 21),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(
+            try_gregorian_to_jalali(2023, 2, 29),
+            Err(JalaliError::InvalidDay {
+                year: 2023,
+                month: 2,
+                day: 29
+            })
+        );
+        assert_eq!(
+            try_gregorian_to_jalali(2023, 13, 1),
+            Err(JalaliError::InvalidMonth { month: 13 })
+        );
+        assert_eq!(
+            try_gregorian_to_jalali(300_000, 1, 1),
+            Err(JalaliError::OutOfRange)
+        );
+        assert_eq!(try_jalali_day_of_year(1403, 12, 30), Ok(366));
+        assert_eq!(try_gregorian_to_jalali(2020, 3, 20), Ok((1399, 1, 1)));
+        assert_eq!(try_jalali_to_gregorian(1399, 1, 1), Ok((2020, 3, 20)));
+    }
+
     #[test]
     fn test_jalali_to_gregorian() {
         // Test case 1: Start of a leap year
@@ -459,6 +691,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_closed_form_jdn_is_contiguous() {
+        // Each year must start right where the previous one ended, and every
+        // JDN must map back to the date it came from.
+        for jy in -40..3000 {
+            let start = jalali_to_jdn_internal(jy, 1, 1);
+            let next_start = jalali_to_jdn_internal(jy + 1, 1, 1);
+            let expected_len = if is_leap(jy) {
+                DAYS_IN_LEAP_YEAR
+            } else {
+                DAYS_IN_COMMON_YEAR
+            };
+            assert_eq!(next_start - start, expected_len, "length of year {}", jy);
+            assert_eq!(jdn_to_jalali_internal(start), (jy, 1, 1));
+            assert_eq!(jdn_to_jalali_internal(next_start - 1).0, jy);
+        }
+        assert_eq!(jalali_to_jdn_internal(1348, 10, 11), UNIX_EPOCH_JDN);
+        assert_eq!(jdn_to_jalali_internal(UNIX_EPOCH_JDN), (1348, 10, 11));
+    }
+
+    #[test]
+    fn test_conversions_with_rule() {
+        // The default rule through the `_with` entry points matches the plain ones.
+        for (gy, gm, gd) in [(2020, 3, 20), (2023, 12, 21), (1991, 8, 6), (622, 3, 22)] {
+            let jalali = try_gregorian_to_jalali_with(&ThirtyThreeYearRule, gy, gm, gd);
+            assert_eq!(jalali, try_gregorian_to_jalali(gy, gm, gd));
+            let (jy, jm, jd) = jalali.unwrap();
+            assert_eq!(
+                try_jalali_to_gregorian_with(&ThirtyThreeYearRule, jy, jm, jd),
+                Ok((gy, gm, gd))
+            );
+            assert_eq!(
+                first_weekday_with(&ThirtyThreeYearRule, jy, jm),
+                first_weekday(jy, jm)
+            );
+        }
+
+        // Birashk makes 1404 leap instead of 1403, so the two calendars
+        // disagree from 30 Esfand 1403 until Nowruz 1405.
+        let birashk = Birashk2820Rule;
+        assert_eq!(try_days_in_month_with(&birashk, 1403, 12), Ok(29));
+        assert_eq!(try_days_in_month_with(&birashk, 1404, 12), Ok(30));
+        assert_eq!(
+            try_jalali_to_gregorian_with(&birashk, 1403, 12, 30),
+            Err(JalaliError::InvalidDay {
+                year: 1403,
+                month: 12,
+                day: 30
+            })
+        );
+        assert_eq!(
+            try_gregorian_to_jalali_with(&birashk, 2025, 3, 20),
+            Ok((1404, 1, 1))
+        );
+        assert_eq!(try_jalali_day_of_year_with(&birashk, 1404, 12, 30), Ok(366));
+
+        // Rules are usable as trait objects.
+        let rules: [&dyn LeapRule; 3] = [&ThirtyThreeYearRule, &Birashk2820Rule, &AstronomicalRule];
+        for rule in rules {
+            assert_eq!(
+                try_jalali_to_gregorian_with(rule, 1400, 1, 1),
+                Ok((2021, 3, 21))
+            );
+            let (gy, gm, gd) = try_jalali_to_gregorian_with(rule, 1380, 7, 15).unwrap();
+            assert_eq!(
+                try_gregorian_to_jalali_with(rule, gy, gm, gd),
+                Ok((1380, 7, 15))
+            );
+        }
+    }
+
     #[test]
     fn test_first_weekday() {
         // 1 Farvardin 1399 was a Friday. (Saturday=0, ..., Friday=6)
@@ -0,0 +1,315 @@
+//! Month and weekday names per locale.
+//!
+//! Iran and Afghanistan share the solar calendar but name its months
+//! differently: Iran uses the old Persian month names (Farvardin, ...),
+//! Afghanistan the zodiac signs (Hamal, Sawr, Jawza, ...) in Dari and their
+//! Pashto equivalents. Weekday tables start on Saturday, as `WEEK_DAYS_AB`.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{
+    JALALI_WEEKDAYS, JALALI_WEEKDAYS_ABBR_ALT, JalaliError, MONTH_NAMES, MONTH_NAMES_ABBR,
+    PERSIAN_MONTH_NAMES, PERSIAN_WEEK_DAYS, PERSIAN_WEEK_DAYS_AB, WEEK_DAYS_AB,
+};
+
+const DARI_MONTH_NAMES: [&str; 12] = [
+    "حمل",
+    "ثور",
+    "جوزا",
+    "سرطان",
+    "اسد",
+    "سنبله",
+    "میزان",
+    "عقرب",
+    "قوس",
+    "جدی",
+    "دلو",
+    "حوت",
+];
+
+const DARI_LATIN_MONTH_NAMES: [&str; 12] = [
+    "Hamal", "Sawr", "Jawza", "Saratan", "Asad", "Sunbula", "Mizan", "Aqrab", "Qaws", "Jadi",
+    "Dalw", "Hut",
+];
+
+const DARI_LATIN_MONTH_NAMES_ABBR: [&str; 12] = [
+    "Ham", "Saw", "Jaw", "Sar", "Asa", "Sun", "Miz", "Aqr", "Qaw", "Jad", "Dal", "Hut",
+];
+
+const DARI_LATIN_WEEKDAYS: [&str; 7] = [
+    "Shanbe",
+    "Yakshanbe",
+    "Dushanbe",
+    "Seshanbe",
+    "Chaharshanbe",
+    "Panjshanbe",
+    "Juma",
+];
+
+const DARI_LATIN_WEEKDAYS_ABBR: [&str; 7] = ["Sha", "Yak", "Dus", "Ses", "Cha", "Pan", "Jum"];
+
+const DARI_LATIN_WEEK_DAYS_AB: [&str; 7] = ["Sh", "Ya", "Du", "Se", "Ch", "Pa", "Ju"];
+
+const PASHTO_MONTH_NAMES: [&str; 12] = [
+    "وری",
+    "غویی",
+    "غبرګولی",
+    "چنګاښ",
+    "زمری",
+    "وږی",
+    "تله",
+    "لړم",
+    "لیندۍ",
+    "مرغومی",
+    "سلواغه",
+    "کب",
+];
+
+const PASHTO_WEEK_DAYS: [&str; 7] = [
+    "شنبه",
+    "يکشنبه",
+    "دوشنبه",
+    "سه‌شنبه",
+    "چارشنبه",
+    "پنجشنبه",
+    "جمعه",
+];
+
+const PASHTO_WEEK_DAYS_AB: [&str; 7] = ["شن", "يک", "دو", "سه", "چا", "پن", "جم"];
+
+const KURDISH_MONTH_NAMES: [&str; 12] = [
+    "خاکەلێوە",
+    "گوڵان",
+    "جۆزەردان",
+    "پووشپەڕ",
+    "گەلاوێژ",
+    "خەرمانان",
+    "ڕەزبەر",
+    "گەڵاڕێزان",
+    "سەرماوەز",
+    "بەفرانبار",
+    "ڕێبەندان",
+    "ڕەشەمە",
+];
+
+const KURDISH_WEEK_DAYS: [&str; 7] = [
+    "شەممە",
+    "یەکشەممە",
+    "دووشەممە",
+    "سێشەممە",
+    "چوارشەممە",
+    "پێنجشەممە",
+    "هەینی",
+];
+
+const KURDISH_WEEK_DAYS_AB: [&str; 7] = ["شە", "یە", "دو", "سێ", "چو", "پێ", "هە"];
+
+/// The language (and script) of month and weekday names.
+///
+/// Locales written in Arabic script have no separate abbreviations; their
+/// abbreviated names are the full names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    /// Iranian names in Latin transliteration (`Farvardin`, `Shanbeh`).
+    #[default]
+    Latin,
+    /// Iranian names in Persian script (`فروردین`, `شنبه`).
+    Persian,
+    /// Afghan zodiac month names in Dari (`حمل`, `ثور`, ...).
+    Dari,
+    /// Afghan zodiac month names transliterated (`Hamal`, `Sawr`, ...).
+    DariLatin,
+    /// Afghan month and weekday names in Pashto (`وری`, `چارشنبه`, ...).
+    Pashto,
+    /// Central Kurdish (Sorani) names (`خاکەلێوە`, `شەممە`, ...).
+    Kurdish,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 6] = [
+        Locale::Latin,
+        Locale::Persian,
+        Locale::Dari,
+        Locale::DariLatin,
+        Locale::Pashto,
+        Locale::Kurdish,
+    ];
+
+    /// Reads a locale tag: one of the names `Display` prints, a language
+    /// code (`fa`, `prs`, `ps`, `ckb`, `ku`), or a POSIX locale such as
+    /// `fa_IR.UTF-8` or `fa_AF`. `C`, `POSIX` and English give `Latin`.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        let tag = tag.to_ascii_lowercase().replace('-', "_");
+        let locale = match tag.as_str() {
+            "fa_af" | "prs" | "prs_af" | "dari" => Locale::Dari,
+            "prs_latn" | "dari_latin" => Locale::DariLatin,
+            "latin" | "c" | "posix" => Locale::Latin,
+            _ => match tag.split('_').next().unwrap_or_default() {
+                "en" => Locale::Latin,
+                "fa" | "persian" => Locale::Persian,
+                "ps" | "pashto" => Locale::Pashto,
+                "ckb" | "ku" | "kurdish" => Locale::Kurdish,
+                _ => return None,
+            },
+        };
+        Some(locale)
+    }
+
+    /// The locale of the first set variable of `LC_ALL`, `LC_TIME` and
+    /// `LANG`, or `Latin` if it is not one of ours.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn month_names(self) -> &'static [&'static str; 12] {
+        match self {
+            Locale::Latin => &MONTH_NAMES,
+            Locale::Persian => &PERSIAN_MONTH_NAMES,
+            Locale::Dari => &DARI_MONTH_NAMES,
+            Locale::DariLatin => &DARI_LATIN_MONTH_NAMES,
+            Locale::Pashto => &PASHTO_MONTH_NAMES,
+            Locale::Kurdish => &KURDISH_MONTH_NAMES,
+        }
+    }
+
+    /// The full names in Arabic-script locales, which have no customary
+    /// abbreviations.
+    pub fn month_names_abbr(self) -> &'static [&'static str; 12] {
+        match self {
+            Locale::Latin => &MONTH_NAMES_ABBR,
+            Locale::DariLatin => &DARI_LATIN_MONTH_NAMES_ABBR,
+            _ => self.month_names(),
+        }
+    }
+
+    pub fn weekday_names(self) -> &'static [&'static str; 7] {
+        match self {
+            Locale::Latin => &JALALI_WEEKDAYS,
+            Locale::Persian | Locale::Dari => &PERSIAN_WEEK_DAYS,
+            Locale::Pashto => &PASHTO_WEEK_DAYS,
+            Locale::DariLatin => &DARI_LATIN_WEEKDAYS,
+            Locale::Kurdish => &KURDISH_WEEK_DAYS,
+        }
+    }
+
+    /// The full names in Arabic-script locales, as `month_names_abbr`.
+    pub fn weekday_names_abbr(self) -> &'static [&'static str; 7] {
+        match self {
+            Locale::Latin => &JALALI_WEEKDAYS_ABBR_ALT,
+            Locale::DariLatin => &DARI_LATIN_WEEKDAYS_ABBR,
+            _ => self.weekday_names(),
+        }
+    }
+
+    /// Two-letter weekday names for calendar headers.
+    pub fn weekday_names_short(self) -> &'static [&'static str; 7] {
+        match self {
+            Locale::Latin => &WEEK_DAYS_AB,
+            Locale::Persian | Locale::Dari => &PERSIAN_WEEK_DAYS_AB,
+            Locale::Pashto => &PASHTO_WEEK_DAYS_AB,
+            Locale::DariLatin => &DARI_LATIN_WEEK_DAYS_AB,
+            Locale::Kurdish => &KURDISH_WEEK_DAYS_AB,
+        }
+    }
+
+    /// Whether numbers are written with Persian digits (۰-۹) in this
+    /// locale.
+    pub fn native_digits(self) -> bool {
+        self.is_arabic_script()
+    }
+
+    /// Whether names are written in Arabic script rather than Latin
+    /// letters: true for Persian, Dari, Pashto and Kurdish.
+    pub fn is_arabic_script(self) -> bool {
+        !matches!(self, Locale::Latin | Locale::DariLatin)
+    }
+
+    /// Whether the language is Persian, as spoken in Iran or as Dari, in
+    /// either script.
+    pub fn is_persian_language(self) -> bool {
+        matches!(self, Locale::Persian | Locale::Dari | Locale::DariLatin)
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Locale::Latin => "latin",
+            Locale::Persian => "fa",
+            Locale::Dari => "prs",
+            Locale::DariLatin => "prs-latn",
+            Locale::Pashto => "ps",
+            Locale::Kurdish => "ckb",
+        })
+    }
+}
+
+impl FromStr for Locale {
+    type Err = JalaliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::from_tag(s).ok_or_else(|| JalaliError::InvalidLocale { tag: s.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_locale_tags() {
+        assert_eq!(Locale::from_tag("fa_IR.UTF-8"), Some(Locale::Persian));
+        assert_eq!(Locale::from_tag("fa_AF.UTF-8"), Some(Locale::Dari));
+        assert_eq!(Locale::from_tag("ps_AF"), Some(Locale::Pashto));
+        assert_eq!(
+            Locale::from_tag("ckb_IQ.UTF-8@latin"),
+            Some(Locale::Kurdish)
+        );
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Some(Locale::Latin));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::Latin));
+        assert_eq!(Locale::from_tag("de_DE"), None);
+        assert!(Locale::Kurdish.is_arabic_script() && !Locale::Kurdish.is_persian_language());
+        assert!(Locale::DariLatin.is_persian_language() && !Locale::DariLatin.is_arabic_script());
+        for locale in Locale::ALL {
+            assert_eq!(locale.to_string().parse::<Locale>(), Ok(locale));
+        }
+        assert_eq!(
+            "xx".parse::<Locale>(),
+            Err(JalaliError::InvalidLocale {
+                tag: "xx".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_locale_names() {
+        // 1403/05/12 is a Friday.
+        let date = ymd(1403, 5, 12);
+        let format = |locale| date.format("%A %-d %B %Y").locale(locale).to_string();
+        assert_eq!(format(Locale::Latin), "Jomeh 12 Mordad 1403");
+        assert_eq!(format(Locale::DariLatin), "Juma 12 Asad 1403");
+        assert_eq!(format(Locale::Dari), "جمعه ۱۲ اسد ۱۴۰۳");
+        assert_eq!(format(Locale::Pashto), "جمعه ۱۲ زمری ۱۴۰۳");
+        assert_eq!(
+            ymd(1403, 5, 10)
+                .format("%A")
+                .locale(Locale::Pashto)
+                .to_string(),
+            "چارشنبه"
+        );
+        assert_eq!(format(Locale::Kurdish), "هەینی ۱۲ گەلاوێژ ۱۴۰۳");
+        assert_eq!(
+            date.format("%a %b").locale(Locale::DariLatin).to_string(),
+            "Jum Asa"
+        );
+    }
+}
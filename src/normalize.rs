@@ -0,0 +1,108 @@
+//! Normalization of text typed with Persian or Arabic keyboards.
+//!
+//! The same date can arrive as `1403/05/10`, `۱۴۰۳/۰۵/۱۰` or `١٤٠٣/٠٥/١٠`,
+//! and the same name with Arabic or Persian yeh and kaf, so input is mapped
+//! to one form before it is parsed.
+
+use std::str::FromStr;
+
+const ZWNJ: char = '\u{200c}';
+
+pub(crate) fn digit_value(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => Some(c as u32 - '0' as u32),
+        '۰'..='۹' => Some(c as u32 - '۰' as u32),
+        '٠'..='٩' => Some(c as u32 - '٠' as u32),
+        _ => None,
+    }
+}
+
+// Case-insensitive, and treats Arabic yeh/kaf as their Persian forms.
+pub(crate) fn fold_char(c: char) -> char {
+    match persian_letter(c) {
+        c if c.is_ascii() => c.to_ascii_lowercase(),
+        c => c,
+    }
+}
+
+fn persian_letter(c: char) -> char {
+    match c {
+        'ي' | 'ى' => 'ی',
+        'ك' => 'ک',
+        _ => c,
+    }
+}
+
+/// Replaces Persian (۰-۹) and Arabic-Indic (٠-٩) digits with ASCII ones;
+/// the inverse of `to_persian_numerals`.
+pub fn to_latin_numerals(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match digit_value(c) {
+            Some(digit) => char::from_digit(digit, 10).expect("digit_value is below 10"),
+            None => c,
+        })
+        .collect()
+}
+
+/// Canonical form of Persian text: ASCII digits, Persian yeh (ی) and kaf
+/// (ک) for their Arabic forms, and zero-width non-joiners only where they
+/// separate two letters. Zero-width spaces, byte order marks and soft
+/// hyphens are dropped.
+///
+/// Pashto writes Arabic yeh as a letter of its own, so this is for Persian
+/// input; the date parser matches names of every locale either way.
+pub fn normalize_persian(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut zwnj = false;
+    for c in to_latin_numerals(input).chars() {
+        match c {
+            '\u{200b}' | '\u{feff}' | '\u{ad}' => {}
+            ZWNJ => zwnj = !out.is_empty(),
+            c => {
+                if zwnj && !c.is_whitespace() && !out.ends_with(char::is_whitespace) {
+                    out.push(ZWNJ);
+                }
+                zwnj = false;
+                out.push(persian_letter(c));
+            }
+        }
+    }
+    out
+}
+
+/// Parses a number written with any digits, e.g. `"۱۴۰۳"`, after
+/// `normalize_persian` and trimming whitespace. Usable as a clap
+/// `value_parser`.
+pub fn parse_number<T: FromStr>(input: &str) -> Result<T, T::Err> {
+    normalize_persian(input).trim().parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_persian() {
+        assert_eq!(to_latin_numerals("۱۴۰۳/٠٥/10"), "1403/05/10");
+        assert_eq!(normalize_persian("علي كريمي"), "علی کریمی");
+        assert_eq!(
+            normalize_persian("سه\u{200c}\u{200c}شنبه"),
+            "سه\u{200c}شنبه"
+        );
+        assert_eq!(
+            normalize_persian("\u{200c}سه \u{200c}شنبه\u{200c} \u{200b}۱"),
+            "سه شنبه 1"
+        );
+        assert_eq!(normalize_persian("\u{feff}1403"), "1403");
+        // Only the letters and digits change; the rest stays as typed.
+        assert_eq!(normalize_persian("Mordad, ۱۲"), "Mordad, 12");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number::<i32>(" ۱۴۰۳ "), Ok(1403));
+        assert_eq!(parse_number::<i64>("-٤٢"), Ok(-42));
+        assert!(parse_number::<u8>("۱۲a").is_err());
+    }
+}
@@ -0,0 +1,717 @@
+//! strptime-style parsing of Jalali date/time strings.
+//!
+//! Patterns use the same specifiers as `JalaliFormat`:
+//!
+//! - `%Y` year, `%m` month number, `%d`/`%e` day, `%j` day of year
+//! - `%B`/`%b`/`%h` month name, full or abbreviated, in any `Locale`
+//!   (Latin names ignore case)
+//! - `%EC` era name (any `Era`, Latin or Persian script) that `%Y` counts
+//!   in, and `%EY` for `%Y %EC`
+//! - `%A`/`%a` weekday name from any weekday table; it must agree with the date
+//! - `%H`/`%k`, `%I`/`%l` with `%p`, `%M`, `%S` time fields and `%N`
+//!   fraction of a second (1-9 digits)
+//! - `%z` offset (`+0330`, `+03:30`, `+03`, `Z`), `%Z` zone (`UTC`, `IRST`,
+//!   or an IANA name such as `Asia/Tehran`)
+//! - `%D`, `%F`, `%T`, `%R`, `%c` as in the formatter, `%n`/`%t` whitespace
+//!   and `%%` a literal percent sign
+//!
+//! Numbers may be written with Latin, Persian (۰-۹) or Arabic-Indic (٠-٩)
+//! digits and need not be zero padded. A space in the pattern matches any
+//! run of whitespace, including none. Padding flags and widths after `%`
+//! (as in `%-d`) are accepted and ignored, so formatter patterns parse back;
+//! the exception is the `~` flag, as numbers spelled out in words are not
+//! read, and a `%~` field fails with a `JalaliError::Parse`.
+
+use std::str::FromStr;
+
+use chrono::{FixedOffset, NaiveTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+use crate::normalize::{digit_value, fold_char};
+use crate::{ENGLISH_WEEK_DAYS_AB, Era, JalaliDate, JalaliError, Locale};
+
+/// Shapes `sdate` and most web forms use, tried in order by `parse_any`.
+pub const COMMON_JALALI_PATTERNS: [&str; 11] = [
+    "%Y/%m/%d",
+    "%Y-%m-%d",
+    "%Y.%m.%d",
+    "%Y/%m/%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%z",
+    "%d %B %Y",
+    "%d %B %Y %H:%M",
+    "%d %B %Y %H:%M:%S",
+    "%A %d %B %Y",
+    "%d %B %EY",
+];
+
+/// Result of parsing: always a date, plus whatever time and zone fields the
+/// pattern contained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JalaliParsed {
+    date: JalaliDate,
+    time: Option<NaiveTime>,
+    offset: Option<FixedOffset>,
+}
+
+impl JalaliParsed {
+    pub fn parse(input: &str, pattern: &str) -> Result<JalaliParsed, JalaliError> {
+        JalaliParsed::parse_with_era(input, pattern, Era::SolarHijri)
+    }
+
+    /// Like `parse`, with years counted in `era` unless the input names
+    /// its era with `%EC`.
+    pub fn parse_with_era(
+        input: &str,
+        pattern: &str,
+        era: Era,
+    ) -> Result<JalaliParsed, JalaliError> {
+        Parser::new(input).run(&expand_composites(pattern), era)
+    }
+
+    /// Tries each pattern in turn. When none matches, the error is the one
+    /// from the pattern that got furthest into the input.
+    pub fn parse_any(input: &str, patterns: &[&str]) -> Result<JalaliParsed, JalaliError> {
+        JalaliParsed::parse_any_with_era(input, patterns, Era::SolarHijri)
+    }
+
+    pub fn parse_any_with_era(
+        input: &str,
+        patterns: &[&str],
+        era: Era,
+    ) -> Result<JalaliParsed, JalaliError> {
+        let mut best: Option<JalaliError> = None;
+        for pattern in patterns {
+            match JalaliParsed::parse_with_era(input, pattern, era) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => {
+                    if best.as_ref().is_none_or(|b| progress(&e) >= progress(b)) {
+                        best = Some(e);
+                    }
+                }
+            }
+        }
+        Err(best.unwrap_or(JalaliError::InvalidFormat {
+            input: input.to_string(),
+        }))
+    }
+
+    pub fn date(&self) -> JalaliDate {
+        self.date
+    }
+
+    pub fn time(&self) -> Option<NaiveTime> {
+        self.time
+    }
+
+    pub fn offset(&self) -> Option<FixedOffset> {
+        self.offset
+    }
+}
+
+impl JalaliDate {
+    /// Parses a date with an explicit pattern, e.g.
+    /// `JalaliDate::parse_from_str("۱۵ مهر ۱۴۰۲", "%d %B %Y")`.
+    pub fn parse_from_str(input: &str, pattern: &str) -> Result<JalaliDate, JalaliError> {
+        JalaliParsed::parse(input, pattern).map(|parsed| parsed.date())
+    }
+}
+
+// Errors past the syntax stage (e.g. 30 Esfand of a common year) mean the
+// pattern matched the whole input.
+fn progress(error: &JalaliError) -> usize {
+    match error {
+        JalaliError::Parse { position, .. } => *position,
+        _ => usize::MAX,
+    }
+}
+
+fn expand_composites(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('D') => out.push_str("%Y/%m/%d"),
+            Some('F') => out.push_str("%Y-%m-%d"),
+            Some('T') => out.push_str("%H:%M:%S"),
+            Some('R') => out.push_str("%H:%M"),
+            Some('c') => out.push_str("%a %B %d %H:%M:%S %Z %Y"),
+            Some('E') if chars.clone().next() == Some('Y') => {
+                chars.next();
+                out.push_str("%Y %EC");
+            }
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+#[derive(Default)]
+struct Fields {
+    year: Option<i32>,
+    era: Option<Era>,
+    month: Option<u8>,
+    day: Option<u8>,
+    ordinal: Option<u16>,
+    weekday: Option<(usize, usize)>,
+    hour: Option<u32>,
+    hour12: Option<u32>,
+    pm: Option<bool>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    nanosecond: Option<u32>,
+    offset: Option<FixedOffset>,
+    zone: Option<(usize, String)>,
+}
+
+struct Parser {
+    input: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Parser {
+        Parser {
+            input: input.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, position: usize, expected: impl Into<String>) -> JalaliError {
+        JalaliError::Parse {
+            position,
+            expected: expected.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn literal(&mut self, expected: char) -> Result<(), JalaliError> {
+        if self.peek().map(fold_char) == Some(fold_char(expected)) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(self.pos, format!("'{}'", expected)))
+        }
+    }
+
+    fn number(
+        &mut self,
+        max_digits: usize,
+        range: std::ops::RangeInclusive<u32>,
+        what: &str,
+    ) -> Result<u32, JalaliError> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        while self.pos - start < max_digits {
+            let Some(digit) = self.peek().and_then(digit_value) else {
+                break;
+            };
+            value = value * 10 + digit;
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error(start, what));
+        }
+        if !range.contains(&value) {
+            return Err(self.error(start, format!("{} {}-{}", what, range.start(), range.end())));
+        }
+        Ok(value)
+    }
+
+    // Longest name (after folding) found at the cursor; returns its index in
+    // the table it came from.
+    fn name(&mut self, tables: &[&[&str]], what: &str) -> Result<usize, JalaliError> {
+        let mut best: Option<(usize, usize)> = None;
+        for table in tables {
+            for (index, name) in table.iter().enumerate() {
+                let len = name.chars().count();
+                let matches = self
+                    .input
+                    .get(self.pos..self.pos + len)
+                    .is_some_and(|slice| {
+                        slice
+                            .iter()
+                            .zip(name.chars())
+                            .all(|(&a, b)| fold_char(a) == fold_char(b))
+                    });
+                if matches && best.is_none_or(|(_, best_len)| len > best_len) {
+                    best = Some((index, len));
+                }
+            }
+        }
+        let (index, len) = best.ok_or_else(|| self.error(self.pos, what))?;
+        self.pos += len;
+        Ok(index)
+    }
+
+    // Digits after the decimal point, scaled to nanoseconds.
+    fn fraction(&mut self) -> Result<u32, JalaliError> {
+        let start = self.pos;
+        let digits = self.number(9, 0..=999_999_999, "fraction of a second")?;
+        Ok(digits * 10u32.pow(9 - (self.pos - start) as u32))
+    }
+
+    fn offset(&mut self) -> Result<FixedOffset, JalaliError> {
+        let start = self.pos;
+        let sign = match self.peek() {
+            Some('Z') | Some('z') => {
+                self.pos += 1;
+                return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+            }
+            Some('+') => 1,
+            Some('-') | Some('−') => -1,
+            _ => return Err(self.error(start, "UTC offset such as +0330")),
+        };
+        self.pos += 1;
+        let hours = self.number(2, 0..=23, "offset hours")?;
+        if self.peek() == Some(':') {
+            self.pos += 1;
+        }
+        let minutes = if self.peek().and_then(digit_value).is_some() {
+            self.number(2, 0..=59, "offset minutes")?
+        } else {
+            0
+        };
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60) as i32)
+            .ok_or_else(|| self.error(start, "UTC offset such as +0330"))
+    }
+
+    fn zone_name(&mut self) -> Result<(usize, String), JalaliError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || "/_-+:".contains(c))
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error(start, "time zone name"));
+        }
+        Ok((start, self.input[start..self.pos].iter().collect()))
+    }
+
+    fn run(mut self, pattern: &str, era: Era) -> Result<JalaliParsed, JalaliError> {
+        let mut fields = Fields::default();
+        let mut spec = pattern.chars().peekable();
+        // Leading whitespace is skipped rather than trimmed, so error
+        // positions count from the start of the original input.
+        self.skip_whitespace();
+        while let Some(c) = spec.next() {
+            if c.is_whitespace() {
+                self.skip_whitespace();
+                continue;
+            }
+            if c != '%' {
+                self.literal(c)?;
+                continue;
+            }
+            // Formatter flags and widths carry no meaning when parsing.
+            let mut words = false;
+            while spec
+                .peek()
+                .is_some_and(|c| "-_0^:~".contains(*c) || c.is_ascii_digit())
+            {
+                words |= spec.next() == Some('~');
+            }
+            let Some(conversion) = spec.next() else {
+                self.literal('%')?;
+                break;
+            };
+            if words {
+                return Err(self.error(
+                    self.pos,
+                    format!(
+                        "a pattern without %~{}, as words are not parsed",
+                        conversion
+                    ),
+                ));
+            }
+            if conversion == 'E' && spec.peek() == Some(&'C') {
+                spec.next();
+                fields.era = Some(Era::ALL[self.name(&Era::names(), "era name")?]);
+                continue;
+            }
+            let next_is_spec = spec.peek() == Some(&'%');
+            self.field(conversion, next_is_spec, &mut fields)?;
+        }
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.error(self.pos, "end of input"));
+        }
+        self.finish(fields, era)
+    }
+
+    fn field(
+        &mut self,
+        conversion: char,
+        next_is_spec: bool,
+        fields: &mut Fields,
+    ) -> Result<(), JalaliError> {
+        match conversion {
+            'Y' => {
+                // Without a separator after it, read at most four digits so
+                // that "14020715" splits as %Y%m%d.
+                let max_digits = if next_is_spec { 4 } else { 6 };
+                fields.year = Some(self.number(max_digits, 1..=999_999, "year")? as i32);
+            }
+            'm' => fields.month = Some(self.number(2, 1..=12, "month")? as u8),
+            'd' | 'e' => {
+                self.skip_whitespace();
+                fields.day = Some(self.number(2, 1..=31, "day")? as u8);
+            }
+            'j' => fields.ordinal = Some(self.number(3, 1..=366, "day of year")? as u16),
+            'B' | 'b' | 'h' => {
+                let tables: Vec<&[&str]> = Locale::ALL
+                    .iter()
+                    .flat_map(|locale| [&locale.month_names()[..], locale.month_names_abbr()])
+                    .collect();
+                fields.month = Some(self.name(&tables, "month name")? as u8 + 1);
+            }
+            'A' | 'a' => {
+                let start = self.pos;
+                let mut tables: Vec<&[&str]> = Locale::ALL
+                    .iter()
+                    .flat_map(|locale| {
+                        [
+                            &locale.weekday_names()[..],
+                            locale.weekday_names_abbr(),
+                            locale.weekday_names_short(),
+                        ]
+                    })
+                    .collect();
+                tables.push(&ENGLISH_WEEK_DAYS_AB);
+                fields.weekday = Some((start, self.name(&tables, "weekday name")?));
+            }
+            'H' | 'k' => {
+                self.skip_whitespace();
+                fields.hour = Some(self.number(2, 0..=23, "hour")?);
+            }
+            'I' | 'l' => {
+                self.skip_whitespace();
+                fields.hour12 = Some(self.number(2, 1..=12, "hour")?);
+            }
+            'p' | 'P' => {
+                let tables: [&[&str]; 1] = [&["AM", "PM", "ق.ظ", "ب.ظ"]];
+                fields.pm = Some(self.name(&tables, "AM or PM")? % 2 == 1);
+            }
+            'M' => fields.minute = Some(self.number(2, 0..=59, "minute")?),
+            'S' => fields.second = Some(self.number(2, 0..=60, "second")?),
+            'N' => fields.nanosecond = Some(self.fraction()?),
+            'z' => fields.offset = Some(self.offset()?),
+            'Z' => fields.zone = Some(self.zone_name()?),
+            'n' | 't' => self.skip_whitespace(),
+            '%' => self.literal('%')?,
+            other => {
+                // Unknown specifiers have to appear literally, as the
+                // formatter would have copied them.
+                self.literal('%')?;
+                self.literal(other)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&self, fields: Fields, era: Era) -> Result<JalaliParsed, JalaliError> {
+        let end = self.pos;
+        let year = fields.year.ok_or_else(|| self.error(end, "a year (%Y)"))?;
+        let year = fields.era.unwrap_or(era).year_to_solar_hijri(year);
+        let date = match (fields.month, fields.day, fields.ordinal) {
+            (Some(month), Some(day), _) => JalaliDate::try_from_ymd(year, month, day)?,
+            (None, None, Some(ordinal)) => JalaliDate::try_from_ordinal(year, ordinal)?,
+            _ => return Err(self.error(end, "a month and day (%m/%B and %d) or %j")),
+        };
+
+        if let Some((position, index)) = fields.weekday {
+            let actual = (date.weekday().num_days_from_sunday() as usize + 1) % 7;
+            if index != actual {
+                return Err(self.error(position, "weekday matching the date"));
+            }
+        }
+
+        let hour = match (fields.hour, fields.hour12, fields.pm) {
+            (Some(hour), _, _) => Some(hour),
+            (None, Some(hour12), pm) => Some(hour12 % 12 + if pm == Some(true) { 12 } else { 0 }),
+            (None, None, _) => None,
+        };
+        let time = if hour.is_some() || fields.minute.is_some() || fields.second.is_some() {
+            // A leap second is kept as nanoseconds past 1e9, as chrono does.
+            let second = fields.second.unwrap_or(0);
+            let nanosecond = fields.nanosecond.unwrap_or(0);
+            NaiveTime::from_hms_nano_opt(
+                hour.unwrap_or(0),
+                fields.minute.unwrap_or(0),
+                second.min(59),
+                if second == 60 {
+                    1_000_000_000 + nanosecond
+                } else {
+                    nanosecond
+                },
+            )
+        } else {
+            None
+        };
+
+        let offset = match (fields.offset, fields.zone) {
+            (Some(offset), _) => Some(offset),
+            (None, Some((position, name))) => Some(self.resolve_zone(position, &name, date, time)?),
+            (None, None) => None,
+        };
+
+        Ok(JalaliParsed { date, time, offset })
+    }
+
+    fn resolve_zone(
+        &self,
+        position: usize,
+        name: &str,
+        date: JalaliDate,
+        time: Option<NaiveTime>,
+    ) -> Result<FixedOffset, JalaliError> {
+        let hours_minutes = |h: i32, m: i32| FixedOffset::east_opt(h * 3600 + m * 60);
+        let fixed = match name.to_ascii_uppercase().as_str() {
+            "UTC" | "GMT" | "Z" => hours_minutes(0, 0),
+            "IRST" => hours_minutes(3, 30),
+            "IRDT" => hours_minutes(4, 30),
+            _ => None,
+        };
+        if let Some(offset) = fixed {
+            return Ok(offset);
+        }
+        // Numeric names such as "+0330", which is what tzdata calls Tehran.
+        let mut numeric = Parser::new(name);
+        if let Ok(offset) = numeric.offset()
+            && numeric.pos == numeric.input.len()
+        {
+            return Ok(offset);
+        }
+        let tz = Tz::from_str(name).map_err(|_| self.error(position, "known time zone"))?;
+        let local = date.to_gregorian().and_time(time.unwrap_or(NaiveTime::MIN));
+        tz.offset_from_local_datetime(&local)
+            .earliest()
+            .map(|offset| offset.fix())
+            .ok_or_else(|| self.error(position, "time that exists in this time zone"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_numeric_patterns() {
+        assert_eq!(
+            JalaliDate::parse_from_str("1402.07.15", "%Y.%m.%d"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("1402/7/5", "%Y/%m/%d"),
+            Ok(ymd(1402, 7, 5))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("14020715", "%Y%m%d"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("1403 366", "%Y %j"),
+            Ok(ymd(1403, 12, 30))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str(" 1402-07-15 ", "%F"),
+            Ok(ymd(1402, 7, 15))
+        );
+    }
+
+    #[test]
+    fn test_names_and_persian_digits() {
+        assert_eq!(
+            JalaliDate::parse_from_str("۱۵ مهر ۱۴۰۲", "%d %B %Y"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("١٥ مهر ١٤٠٢", "%d %B %Y"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("15 mehr 1402", "%d %B %Y"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("Far 1, 1403", "%b %d, %Y"),
+            Ok(ymd(1403, 1, 1))
+        );
+        // Arabic yeh in "فروردين".
+        assert_eq!(
+            JalaliDate::parse_from_str("1 فروردين 1403", "%d %B %Y"),
+            Ok(ymd(1403, 1, 1))
+        );
+        // 15 Mehr 1402 was a Saturday.
+        assert_eq!(
+            JalaliDate::parse_from_str("Shanbeh 15 Mehr 1402", "%A %d %B %Y"),
+            Ok(ymd(1402, 7, 15))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("شنبه ۱۵ مهر ۱۴۰۲", "%A %d %B %Y"),
+            Ok(ymd(1402, 7, 15))
+        );
+        // Afghan and Kurdish names of 12 Mordad 1403, a Friday.
+        assert_eq!(
+            JalaliDate::parse_from_str("Juma 12 Asad 1403", "%A %d %B %Y"),
+            Ok(ymd(1403, 5, 12))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("۱۲ زمری ۱۴۰۳", "%d %B %Y"),
+            Ok(ymd(1403, 5, 12))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("هەینی ۱۲ گەلاوێژ ۱۴۰۳", "%A %d %B %Y"),
+            Ok(ymd(1403, 5, 12))
+        );
+    }
+
+    #[test]
+    fn test_time_and_zone() {
+        let parsed = JalaliParsed::parse("15 Mehr 1402 14:30", "%d %B %Y %H:%M").unwrap();
+        assert_eq!(parsed.date(), ymd(1402, 7, 15));
+        assert_eq!(parsed.time(), NaiveTime::from_hms_opt(14, 30, 0));
+        assert_eq!(parsed.offset(), None);
+
+        let parsed = JalaliParsed::parse("1402/07/15 02:05 ب.ظ", "%Y/%m/%d %I:%M %p").unwrap();
+        assert_eq!(parsed.time(), NaiveTime::from_hms_opt(14, 5, 0));
+
+        let parsed = JalaliParsed::parse("1402-07-15T14:30:00+03:30", "%FT%T%z").unwrap();
+        assert_eq!(parsed.offset(), FixedOffset::east_opt(12_600));
+
+        let parsed = JalaliParsed::parse("1402/07/15 14:30:05.25", "%D %T.%N").unwrap();
+        assert_eq!(parsed.time(), NaiveTime::from_hms_milli_opt(14, 30, 5, 250));
+
+        let parsed = JalaliParsed::parse("1402/07/15 08:00 Asia/Tehran", "%D %R %Z").unwrap();
+        assert_eq!(parsed.offset(), FixedOffset::east_opt(12_600));
+        // Iran observed daylight saving time until 1401.
+        let parsed = JalaliParsed::parse("1399/03/01 08:00 Asia/Tehran", "%D %R %Z").unwrap();
+        assert_eq!(parsed.offset(), FixedOffset::east_opt(16_200));
+    }
+
+    #[test]
+    fn test_formatter_output_parses_back() {
+        let original = ymd(1403, 5, 7);
+        for pattern in ["%c", "%A %-d %B %Y", "%a, %-d %b %Y %T %z", "%D"] {
+            let text = original.format(pattern).to_string();
+            assert_eq!(
+                JalaliDate::parse_from_str(&text, pattern),
+                Ok(original),
+                "{}",
+                pattern
+            );
+        }
+        let tehran = original
+            .format("%c")
+            .with_zone(FixedOffset::east_opt(12_600).unwrap(), "+0330")
+            .to_string();
+        let parsed = JalaliParsed::parse(&tehran, "%c").unwrap();
+        assert_eq!(parsed.offset(), FixedOffset::east_opt(12_600));
+
+        let persian = original
+            .format("%A %d %B %Y")
+            .persian_digits(true)
+            .persian_names(true)
+            .to_string();
+        assert_eq!(
+            JalaliDate::parse_from_str(&persian, "%A %d %B %Y"),
+            Ok(original)
+        );
+    }
+
+    #[test]
+    fn test_errors_report_position() {
+        let parse_error = |position, expected: &str| JalaliError::Parse {
+            position,
+            expected: expected.to_string(),
+        };
+        assert_eq!(
+            JalaliDate::parse_from_str("1402/13/01", "%Y/%m/%d"),
+            Err(parse_error(5, "month 1-12"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("1402-07-15", "%Y/%m/%d"),
+            Err(parse_error(4, "'/'"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("15 Mehrr 1402", "%d %B %Y"),
+            Err(parse_error(7, "year"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("15 Mihr 1402", "%d %B %Y"),
+            Err(parse_error(3, "month name"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("1402/07/15 extra", "%Y/%m/%d"),
+            Err(parse_error(11, "end of input"))
+        );
+        assert_eq!(
+            JalaliParsed::parse("  1403/05/1x", "%Y/%m/%d").map(|parsed| parsed.date()),
+            Err(parse_error(11, "end of input"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("Jomeh 15 Mehr 1402", "%A %d %B %Y"),
+            Err(parse_error(0, "weekday matching the date"))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("seventh of Mordad", "%~d of %B"),
+            Err(parse_error(
+                0,
+                "a pattern without %~d, as words are not parsed"
+            ))
+        );
+        assert_eq!(
+            JalaliDate::parse_from_str("1400/12/30", "%Y/%m/%d"),
+            Err(JalaliError::InvalidDay {
+                year: 1400,
+                month: 12,
+                day: 30
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_any() {
+        for input in [
+            "1402/07/15",
+            "1402.7.15",
+            "۱۵ مهر ۱۴۰۲",
+            "15 Mehr 1402 14:30",
+        ] {
+            assert_eq!(
+                JalaliParsed::parse_any(input, &COMMON_JALALI_PATTERNS).map(|p| p.date()),
+                Ok(ymd(1402, 7, 15)),
+                "{}",
+                input
+            );
+        }
+        // The furthest-reaching pattern explains the failure.
+        assert_eq!(
+            JalaliParsed::parse_any("1402/07/15 25:00", &COMMON_JALALI_PATTERNS),
+            Err(JalaliError::Parse {
+                position: 11,
+                expected: "hour 0-23".to_string()
+            })
+        );
+    }
+}
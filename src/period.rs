@@ -0,0 +1,150 @@
+//! Difference between two Jalali dates.
+
+use std::fmt;
+
+use crate::{JalaliDate, MONTHS_PER_YEAR_COUNT, MonthEndPolicy};
+
+/// Calendar difference between two Jalali dates, as whole years, months and
+/// leftover days plus the exact number of days.
+///
+/// All components carry the same sign: negative when the end date is before
+/// the start date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct JalaliPeriod {
+    years: i32,
+    months: i32,
+    days: i32,
+    total_days: i64,
+}
+
+impl JalaliPeriod {
+    /// Period from `start` to `end`. Months are counted on the Jalali fields
+    /// and only complete months count, so 31 Shahrivar to 30 Mehr is 30 days
+    /// rather than one month.
+    pub fn between(start: JalaliDate, end: JalaliDate) -> JalaliPeriod {
+        if end < start {
+            return -JalaliPeriod::between(end, start);
+        }
+
+        let months_per_year = MONTHS_PER_YEAR_COUNT as i64;
+        let month_index =
+            |date: JalaliDate| date.year() as i64 * months_per_year + date.month() as i64;
+        let mut months = month_index(end) - month_index(start);
+        if end.day() < start.day() {
+            months -= 1;
+        }
+        // With the adjustment above the anchor never passes `end`, and both
+        // dates are in range, so the addition cannot fail.
+        let anchor = start
+            .checked_add_months(months, MonthEndPolicy::Clamp)
+            .expect("anchor lies between two valid dates");
+
+        JalaliPeriod {
+            years: (months / months_per_year) as i32,
+            months: (months % months_per_year) as i32,
+            days: (end.to_jdn() - anchor.to_jdn()) as i32,
+            total_days: end.to_jdn() - start.to_jdn(),
+        }
+    }
+
+    pub fn years(&self) -> i32 {
+        self.years
+    }
+
+    /// Months left over after whole years (0-11).
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Days left over after whole months.
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn total_days(&self) -> i64 {
+        self.total_days
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.total_days < 0
+    }
+}
+
+impl std::ops::Neg for JalaliPeriod {
+    type Output = JalaliPeriod;
+
+    fn neg(self) -> JalaliPeriod {
+        JalaliPeriod {
+            years: -self.years,
+            months: -self.months,
+            days: -self.days,
+            total_days: -self.total_days,
+        }
+    }
+}
+
+impl JalaliDate {
+    /// Signed number of days from `self` to `other`.
+    pub fn days_until(self, other: JalaliDate) -> i64 {
+        other.to_jdn() - self.to_jdn()
+    }
+
+    pub fn period_until(self, other: JalaliDate) -> JalaliPeriod {
+        JalaliPeriod::between(self, other)
+    }
+}
+
+// e.g. "3 years, 4 months, 11 days"
+impl fmt::Display for JalaliPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: i32| if n.abs() == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} year{}, {} month{}, {} day{}",
+            self.years,
+            plural(self.years),
+            self.months,
+            plural(self.months),
+            self.days,
+            plural(self.days)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_period_between() {
+        let period = JalaliPeriod::between(ymd(1399, 12, 30), ymd(1403, 5, 10));
+        assert_eq!((period.years(), period.months(), period.days()), (3, 4, 11));
+        assert_eq!(period.total_days(), 1229);
+        assert_eq!(period.to_string(), "3 years, 4 months, 11 days");
+
+        // A month is only complete once its day number is reached.
+        let period = ymd(1403, 6, 31).period_until(ymd(1403, 7, 30));
+        assert_eq!((period.years(), period.months(), period.days()), (0, 0, 30));
+        let period = ymd(1403, 1, 15).period_until(ymd(1404, 1, 15));
+        assert_eq!((period.years(), period.months(), period.days()), (1, 0, 0));
+        assert_eq!(period.total_days(), 366);
+
+        assert_eq!(
+            JalaliPeriod::between(ymd(1400, 1, 1), ymd(1400, 1, 1)),
+            JalaliPeriod::default()
+        );
+    }
+
+    #[test]
+    fn test_period_is_antisymmetric() {
+        let a = ymd(1380, 8, 20);
+        let b = ymd(1402, 2, 3);
+        let forward = a.period_until(b);
+        let backward = b.period_until(a);
+        assert_eq!(backward, -forward);
+        assert!(backward.is_negative());
+        assert_eq!(a.days_until(b), forward.total_days());
+        assert_eq!(backward.to_string(), "-21 years, -5 months, -14 days");
+    }
+}
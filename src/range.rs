@@ -0,0 +1,332 @@
+//! Iterators over sequences of Jalali dates.
+
+use std::iter::FusedIterator;
+
+use crate::{JalaliDate, JalaliError, MONTHS_PER_YEAR_COUNT, is_leap, try_days_in_month};
+
+/// Distance between consecutive dates of a `JalaliDateRange`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeStep {
+    Days(u32),
+    Weeks(u32),
+    /// Jalali months; a day past the end of a shorter month is clamped, but
+    /// every date is counted from the start, so 31 Shahrivar steps to
+    /// 30 Mehr and then 31 Farvardin of the next year.
+    Months(u32),
+    Years(u32),
+}
+
+impl RangeStep {
+    // Days per step for day-based steps, months per step otherwise.
+    fn split(self) -> (Option<i64>, i64) {
+        match self {
+            RangeStep::Days(n) => (Some(n as i64), 0),
+            RangeStep::Weeks(n) => (Some(7 * n as i64), 0),
+            RangeStep::Months(n) => (None, n as i64),
+            RangeStep::Years(n) => (None, MONTHS_PER_YEAR_COUNT as i64 * n as i64),
+        }
+    }
+}
+
+/// A finite, double-ended sequence of Jalali dates.
+///
+/// The `n`th date is worked out from the first one rather than from its
+/// neighbour, so both ends can be taken in constant time and the length
+/// is known up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JalaliDateRange {
+    // Day-based ranges count from `first_jdn`; month-based ranges from
+    // `first_month` (year * 12 + month - 1) with `day`, which may be 31
+    // for month ends and is clamped to each month's length.
+    first_jdn: i64,
+    first_month: i64,
+    day: u8,
+    step: RangeStep,
+    front: u64,
+    back: u64,
+}
+
+impl JalaliDateRange {
+    /// Dates from `start` to `end`, both included, `step` apart.
+    ///
+    /// Panics if the step is zero, like `Iterator::step_by`.
+    pub fn inclusive(start: JalaliDate, end: JalaliDate, step: RangeStep) -> JalaliDateRange {
+        JalaliDateRange::anchored(start.year(), start.month(), start.day(), step, end, true)
+    }
+
+    /// Dates from `start` up to but not including `end`, `step` apart.
+    ///
+    /// Panics if the step is zero.
+    pub fn exclusive(start: JalaliDate, end: JalaliDate, step: RangeStep) -> JalaliDateRange {
+        JalaliDateRange::anchored(start.year(), start.month(), start.day(), step, end, false)
+    }
+
+    /// Every day of a Jalali month.
+    pub fn month(year: i32, month: u8) -> Result<JalaliDateRange, JalaliError> {
+        let days = try_days_in_month(year, month)?;
+        Ok(JalaliDateRange::inclusive(
+            JalaliDate::try_from_ymd(year, month, 1)?,
+            JalaliDate::try_from_ymd(year, month, days)?,
+            RangeStep::Days(1),
+        ))
+    }
+
+    /// Every day of a Jalali year, 365 or 366 of them.
+    pub fn year(year: i32) -> Result<JalaliDateRange, JalaliError> {
+        let last = if is_leap(year) { 30 } else { 29 };
+        Ok(JalaliDateRange::inclusive(
+            JalaliDate::try_from_ymd(year, 1, 1)?,
+            JalaliDate::try_from_ymd(year, 12, last)?,
+            RangeStep::Days(1),
+        ))
+    }
+
+    /// The first day of each month that starts between `start` and `end`
+    /// (inclusive).
+    pub fn month_starts(start: JalaliDate, end: JalaliDate) -> JalaliDateRange {
+        let mut range = JalaliDateRange::anchored(
+            start.year(),
+            start.month(),
+            1,
+            RangeStep::Months(1),
+            end,
+            true,
+        );
+        if start.day() > 1 && range.front < range.back {
+            range.front += 1;
+        }
+        range
+    }
+
+    /// The last day of each month that ends between `start` and `end`
+    /// (inclusive).
+    pub fn month_ends(start: JalaliDate, end: JalaliDate) -> JalaliDateRange {
+        JalaliDateRange::anchored(
+            start.year(),
+            start.month(),
+            31,
+            RangeStep::Months(1),
+            end,
+            true,
+        )
+    }
+
+    fn anchored(
+        year: i32,
+        month: u8,
+        day: u8,
+        step: RangeStep,
+        end: JalaliDate,
+        inclusive: bool,
+    ) -> JalaliDateRange {
+        let (step_days, step_months) = step.split();
+        assert!(
+            step_days.unwrap_or(step_months) > 0,
+            "range step must be positive"
+        );
+        let mut range = JalaliDateRange {
+            first_jdn: 0,
+            first_month: month_index(year, month),
+            day,
+            step,
+            front: 0,
+            back: 0,
+        };
+        range.first_jdn = range.month_date(0).to_jdn();
+
+        // Index of the last date that is still within bounds.
+        let last = match step_days {
+            Some(step_days) => {
+                let span = end.to_jdn() - range.first_jdn - if inclusive { 0 } else { 1 };
+                if span < 0 { -1 } else { span / step_days }
+            }
+            None => {
+                let span = month_index(end.year(), end.month()) - range.first_month;
+                let mut last = if span < 0 { -1 } else { span / step_months };
+                let in_bounds = |date: JalaliDate| if inclusive { date <= end } else { date < end };
+                while last >= 0 && !in_bounds(range.month_date(last as u64)) {
+                    last -= 1;
+                }
+                last
+            }
+        };
+        range.back = (last + 1) as u64;
+        range
+    }
+
+    // The `index`th date of a month-based range.
+    fn month_date(&self, index: u64) -> JalaliDate {
+        let (_, step_months) = self.step.split();
+        let months = self.first_month + index as i64 * step_months;
+        let months_per_year = MONTHS_PER_YEAR_COUNT as i64;
+        let year = months.div_euclid(months_per_year) as i32;
+        let month = months.rem_euclid(months_per_year) as u8 + 1;
+        let days = try_days_in_month(year, month).expect("month index maps to a valid month");
+        JalaliDate::try_from_ymd(year, month, self.day.min(days))
+            .expect("dates within the range bounds are valid")
+    }
+
+    fn date(&self, index: u64) -> JalaliDate {
+        match self.step.split() {
+            (Some(step_days), _) => {
+                JalaliDate::try_from_jdn(self.first_jdn + index as i64 * step_days)
+                    .expect("dates within the range bounds are valid")
+            }
+            (None, _) => self.month_date(index),
+        }
+    }
+}
+
+fn month_index(year: i32, month: u8) -> i64 {
+    year as i64 * MONTHS_PER_YEAR_COUNT as i64 + month as i64 - 1
+}
+
+impl Iterator for JalaliDateRange {
+    type Item = JalaliDate;
+
+    fn next(&mut self) -> Option<JalaliDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        let date = self.date(self.front);
+        self.front += 1;
+        Some(date)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<JalaliDate> {
+        self.front = self.front.saturating_add(n as u64).min(self.back);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for JalaliDateRange {
+    fn next_back(&mut self) -> Option<JalaliDate> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.date(self.back))
+    }
+}
+
+impl ExactSizeIterator for JalaliDateRange {}
+
+impl FusedIterator for JalaliDateRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_month_and_year_days() {
+        let mehr = JalaliDateRange::month(1403, 7).unwrap();
+        assert_eq!(mehr.len(), 30);
+        assert_eq!(mehr.clone().next(), Some(ymd(1403, 7, 1)));
+        assert_eq!(mehr.clone().next_back(), Some(ymd(1403, 7, 30)));
+        assert_eq!(JalaliDateRange::month(1403, 12).unwrap().len(), 30);
+        assert!(JalaliDateRange::month(1403, 13).is_err());
+
+        assert_eq!(JalaliDateRange::year(1403).unwrap().len(), 366);
+        let year = JalaliDateRange::year(1404).unwrap();
+        assert_eq!(year.len(), 365);
+        assert!(
+            year.clone()
+                .zip(year.skip(1))
+                .all(|(a, b)| a.days_until(b) == 1)
+        );
+    }
+
+    #[test]
+    fn test_day_steps() {
+        let start = ymd(1403, 12, 25);
+        let end = ymd(1404, 1, 5);
+        assert_eq!(
+            JalaliDateRange::inclusive(start, end, RangeStep::Days(1)).len(),
+            11
+        );
+        assert_eq!(
+            JalaliDateRange::exclusive(start, end, RangeStep::Days(1)).len(),
+            10
+        );
+
+        let weekly: Vec<_> = JalaliDateRange::inclusive(start, end, RangeStep::Weeks(1)).collect();
+        assert_eq!(weekly, [start, ymd(1404, 1, 2)]);
+        let mut every_third = JalaliDateRange::inclusive(start, end, RangeStep::Days(3));
+        assert_eq!(every_third.len(), 4);
+        assert_eq!(every_third.next_back(), Some(ymd(1404, 1, 4)));
+        assert_eq!(every_third.nth(1), Some(ymd(1403, 12, 28)));
+        assert_eq!(every_third.len(), 1);
+
+        assert_eq!(
+            JalaliDateRange::inclusive(end, start, RangeStep::Days(1)).len(),
+            0
+        );
+        assert_eq!(
+            JalaliDateRange::exclusive(start, start, RangeStep::Days(1)).len(),
+            0
+        );
+        assert_eq!(
+            JalaliDateRange::inclusive(start, start, RangeStep::Days(1)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_month_and_year_steps() {
+        let start = ymd(1403, 6, 31);
+        let monthly: Vec<_> =
+            JalaliDateRange::inclusive(start, ymd(1403, 9, 30), RangeStep::Months(1)).collect();
+        assert_eq!(
+            monthly,
+            [start, ymd(1403, 7, 30), ymd(1403, 8, 30), ymd(1403, 9, 30)]
+        );
+        assert_eq!(
+            JalaliDateRange::exclusive(start, ymd(1403, 9, 30), RangeStep::Months(1)).len(),
+            3
+        );
+
+        let mut quarterly =
+            JalaliDateRange::inclusive(ymd(1403, 1, 1), ymd(1403, 12, 30), RangeStep::Months(3));
+        assert_eq!(quarterly.next_back(), Some(ymd(1403, 10, 1)));
+
+        let leap_days: Vec<_> =
+            JalaliDateRange::inclusive(ymd(1399, 12, 30), ymd(1408, 1, 1), RangeStep::Years(4))
+                .collect();
+        assert_eq!(
+            leap_days,
+            [ymd(1399, 12, 30), ymd(1403, 12, 30), ymd(1407, 12, 29)]
+        );
+    }
+
+    #[test]
+    fn test_month_starts_and_ends() {
+        let start = ymd(1403, 5, 10);
+        let end = ymd(1403, 8, 30);
+        let starts: Vec<_> = JalaliDateRange::month_starts(start, end).collect();
+        assert_eq!(starts, [ymd(1403, 6, 1), ymd(1403, 7, 1), ymd(1403, 8, 1)]);
+        let ends: Vec<_> = JalaliDateRange::month_ends(start, end).collect();
+        assert_eq!(
+            ends,
+            [
+                ymd(1403, 5, 31),
+                ymd(1403, 6, 31),
+                ymd(1403, 7, 30),
+                ymd(1403, 8, 30)
+            ]
+        );
+
+        let mut year_ends = JalaliDateRange::month_ends(ymd(1404, 1, 1), ymd(1404, 12, 29));
+        assert_eq!(year_ends.len(), 12);
+        assert_eq!(year_ends.next_back(), Some(ymd(1404, 12, 29)));
+        assert_eq!(
+            JalaliDateRange::month_starts(ymd(1403, 5, 2), ymd(1403, 5, 31)).len(),
+            0
+        );
+    }
+}
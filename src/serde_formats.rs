@@ -0,0 +1,200 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! `JalaliDate` is written as an ISO-like string, `"1403-05-10"`, and read
+//! back from that or from `"1403/05/10"`. `JalaliDateTime` is written as
+//! `"1403-05-10T14:30:00+03:30"` (with `.%N` nanoseconds when non-zero)
+//! and can be read into `JalaliDateTime<FixedOffset>`, `<Utc>` or
+//! `<Local>`.
+//!
+//! Other representations of a `JalaliDate` field are picked with
+//! `#[serde(with = "...")]`:
+//!
+//! - `scal_lib::serde_formats::ymd_tuple`: `[1403, 5, 10]`
+//! - `scal_lib::serde_formats::jdn`: the Julian Day Number, `2460523`
+
+use std::fmt;
+
+use chrono::{FixedOffset, Local, NaiveTime, Offset, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{DstPolicy, JalaliDate, JalaliDateTime, JalaliParsed};
+
+const DATETIME_PATTERNS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%z", "%Y-%m-%dT%H:%M:%S.%N%z"];
+
+impl Serialize for JalaliDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.format("%F"))
+    }
+}
+
+impl<'de> Deserialize<'de> for JalaliDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<JalaliDate, D::Error> {
+        deserializer.deserialize_str(DateVisitor)
+    }
+}
+
+struct DateVisitor;
+
+impl de::Visitor<'_> for DateVisitor {
+    type Value = JalaliDate;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a Jalali date string such as \"1403-05-10\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<JalaliDate, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+impl<Tz: TimeZone> Serialize for JalaliDateTime<Tz> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let pattern = if self.time().nanosecond() == 0 {
+            "%FT%T%:z"
+        } else {
+            "%FT%T.%N%:z"
+        };
+        let text = self
+            .date()
+            .format(pattern)
+            .with_time(self.time())
+            .with_zone(self.offset().fix(), "");
+        serializer.collect_str(&text)
+    }
+}
+
+struct DateTimeVisitor;
+
+impl de::Visitor<'_> for DateTimeVisitor {
+    type Value = JalaliDateTime<FixedOffset>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a Jalali date-time string such as \"1403-05-10T14:30:00+03:30\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<JalaliDateTime<FixedOffset>, E> {
+        let parsed = JalaliParsed::parse_any(value, &DATETIME_PATTERNS).map_err(E::custom)?;
+        // Both patterns have a time and an offset.
+        let time = parsed.time().unwrap_or(NaiveTime::MIN);
+        let offset = parsed.offset().unwrap_or(Utc.fix());
+        JalaliDateTime::from_local(parsed.date(), time, &offset, DstPolicy::Error)
+            .map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for JalaliDateTime<FixedOffset> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(DateTimeVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for JalaliDateTime<Utc> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_str(DateTimeVisitor)?
+            .with_timezone(&Utc)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for JalaliDateTime<Local> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_str(DateTimeVisitor)?
+            .with_timezone(&Local)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// `JalaliDate` as a `(year, month, day)` tuple.
+pub mod ymd_tuple {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::JalaliDate;
+
+    pub fn serialize<S: Serializer>(date: &JalaliDate, serializer: S) -> Result<S::Ok, S::Error> {
+        (date.year(), date.month(), date.day()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<JalaliDate, D::Error> {
+        let (year, month, day) = <(i32, u8, u8)>::deserialize(deserializer)?;
+        JalaliDate::try_from_ymd(year, month, day).map_err(de::Error::custom)
+    }
+}
+
+/// `JalaliDate` as its Julian Day Number.
+pub mod jdn {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    use crate::JalaliDate;
+
+    pub fn serialize<S: Serializer>(date: &JalaliDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(date.to_jdn())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<JalaliDate, D::Error> {
+        JalaliDate::try_from_jdn(i64::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+    use chrono::FixedOffset;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        text: JalaliDate,
+        #[serde(with = "ymd_tuple")]
+        tuple: JalaliDate,
+        #[serde(with = "jdn")]
+        jdn: JalaliDate,
+    }
+
+    #[test]
+    fn test_date_representations_round_trip() {
+        let date = ymd(1403, 5, 10);
+        let record = Record {
+            text: date,
+            tuple: date,
+            jdn: date,
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            r#"{"text":"1403-05-10","tuple":[1403,5,10],"jdn":2460523}"#
+        );
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+
+        assert_eq!(
+            serde_json::from_str::<JalaliDate>(r#""1403/5/10""#).unwrap(),
+            date
+        );
+        assert!(serde_json::from_str::<JalaliDate>(r#""1403-12-30""#).is_ok());
+        assert!(serde_json::from_str::<JalaliDate>(r#""1404-12-30""#).is_err());
+        assert!(
+            serde_json::from_str::<Record>(r#"{"text":"1403-05-10","tuple":[1403,13,1],"jdn":0}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_datetime_round_trip() {
+        let tehran = FixedOffset::east_opt(12_600).unwrap();
+        let datetime = JalaliDateTime::from_timestamp(1_722_665_130, 0, &tehran).unwrap();
+        let json = serde_json::to_string(&datetime).unwrap();
+        assert_eq!(json, r#""1403-05-13T09:35:30+03:30""#);
+        assert_eq!(
+            serde_json::from_str::<JalaliDateTime<FixedOffset>>(&json).unwrap(),
+            datetime
+        );
+
+        let precise = JalaliDateTime::from_timestamp(1_722_665_130, 120_000_000, &Utc).unwrap();
+        let json = serde_json::to_string(&precise).unwrap();
+        assert_eq!(json, r#""1403-05-13T06:05:30.120000000+00:00""#);
+        let back: JalaliDateTime<Utc> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, precise);
+        assert_eq!(back.timestamp_subsec_nanos(), 120_000_000);
+    }
+}
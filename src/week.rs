@@ -0,0 +1,252 @@
+//! Week-of-year numbering for the Jalali calendar.
+
+use std::fmt;
+
+use chrono::Weekday;
+
+use crate::{JalaliDate, JalaliError, MAX_JALALI_YEAR, MIN_JALALI_YEAR, jalali_to_jdn_internal};
+
+const DAYS_PER_WEEK: i64 = 7;
+
+/// Which week of a year is week 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FirstWeek {
+    /// The week that contains 1 Farvardin; the last days of Esfand in that
+    /// week belong to week 1 of the next year.
+    #[default]
+    ContainsNewYear,
+    /// The first week that lies entirely in the year; days of Farvardin
+    /// before it belong to the last week of the previous year.
+    FirstFullWeek,
+}
+
+/// A week numbering scheme: the day weeks start on and how week 1 is chosen.
+///
+/// The default is the usual Iranian one, Saturday-based weeks with week 1
+/// containing Nowruz.
+///
+/// Week-based years stay within `MIN_JALALI_YEAR..=MAX_JALALI_YEAR`: week 1
+/// of the first year always contains 1 Farvardin, and the last year keeps
+/// its final days instead of handing them to the year after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeekRule {
+    week_start: Weekday,
+    first_week: FirstWeek,
+}
+
+/// A week of a week-based year, e.g. `1403-W42`.
+///
+/// The week-based year can differ from the calendar year for dates near
+/// Nowruz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JalaliWeek {
+    year: i32,
+    week: u32,
+}
+
+impl JalaliWeek {
+    /// The week-based year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Week number, starting at 1.
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+}
+
+impl fmt::Display for JalaliWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year, self.week)
+    }
+}
+
+impl Default for WeekRule {
+    fn default() -> WeekRule {
+        WeekRule::new(Weekday::Sat, FirstWeek::ContainsNewYear)
+    }
+}
+
+impl WeekRule {
+    pub const fn new(week_start: Weekday, first_week: FirstWeek) -> WeekRule {
+        WeekRule {
+            week_start,
+            first_week,
+        }
+    }
+
+    pub fn week_start(&self) -> Weekday {
+        self.week_start
+    }
+
+    pub fn first_week(&self) -> FirstWeek {
+        self.first_week
+    }
+
+    // JDN of the first day of the week that contains `jdn`.
+    fn week_start_jdn(&self, jdn: i64) -> i64 {
+        // JDN 0 was a Monday.
+        jdn - (jdn - self.week_start.num_days_from_monday() as i64).rem_euclid(DAYS_PER_WEEK)
+    }
+
+    // JDN of the first day of week 1 of `year`.
+    fn week_one_jdn(&self, year: i32) -> i64 {
+        let new_year = jalali_to_jdn_internal(year, 1, 1);
+        let week_start = self.week_start_jdn(new_year);
+        match self.first_week {
+            FirstWeek::FirstFullWeek if week_start != new_year && year > MIN_JALALI_YEAR => {
+                week_start + DAYS_PER_WEEK
+            }
+            _ => week_start,
+        }
+    }
+
+    // JDN of the first day after the last week of `year`.
+    fn week_end_jdn(&self, year: i32) -> i64 {
+        if year == MAX_JALALI_YEAR {
+            let last_day = jalali_to_jdn_internal(year + 1, 1, 1) - 1;
+            self.week_start_jdn(last_day) + DAYS_PER_WEEK
+        } else {
+            self.week_one_jdn(year + 1)
+        }
+    }
+
+    pub fn week_of_year(&self, date: JalaliDate) -> JalaliWeek {
+        let jdn = date.to_jdn();
+        let mut year = date.year();
+        if jdn >= self.week_end_jdn(year) {
+            year += 1;
+        } else if jdn < self.week_one_jdn(year) {
+            year -= 1;
+        }
+        JalaliWeek {
+            year,
+            week: ((jdn - self.week_one_jdn(year)) / DAYS_PER_WEEK) as u32 + 1,
+        }
+    }
+
+    /// 52 or 53; up to 54 for the first and last supported years.
+    pub fn weeks_in_year(&self, year: i32) -> u32 {
+        ((self.week_end_jdn(year) - self.week_one_jdn(year)) / DAYS_PER_WEEK) as u32
+    }
+
+    /// The `weekday` of week `week` of week-based year `year`.
+    pub fn date_from_week(
+        &self,
+        year: i32,
+        week: u32,
+        weekday: Weekday,
+    ) -> Result<JalaliDate, JalaliError> {
+        if !(MIN_JALALI_YEAR..=MAX_JALALI_YEAR).contains(&year) {
+            return Err(JalaliError::OutOfRange);
+        }
+        if week == 0 || week > self.weeks_in_year(year) {
+            return Err(JalaliError::InvalidWeek { year, week });
+        }
+        let offset = (weekday.num_days_from_monday() as i64
+            - self.week_start.num_days_from_monday() as i64)
+            .rem_euclid(DAYS_PER_WEEK);
+        JalaliDate::try_from_jdn(
+            self.week_one_jdn(year) + (week as i64 - 1) * DAYS_PER_WEEK + offset,
+        )
+    }
+}
+
+impl JalaliDate {
+    pub fn week_of_year(&self, rule: WeekRule) -> JalaliWeek {
+        rule.week_of_year(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::ymd;
+
+    #[test]
+    fn test_week_containing_nowruz() {
+        let rule = WeekRule::default();
+        // 1 Farvardin 1404 was a Friday, so its week began on 25 Esfand 1403.
+        let week = ymd(1404, 1, 1).week_of_year(rule);
+        assert_eq!((week.year(), week.week()), (1404, 1));
+        assert_eq!(ymd(1403, 12, 25).week_of_year(rule), week);
+        assert_eq!(ymd(1403, 12, 24).week_of_year(rule).to_string(), "1403-W52");
+        assert_eq!(ymd(1404, 1, 2).week_of_year(rule).week(), 2);
+
+        assert_eq!(
+            rule.date_from_week(1404, 1, Weekday::Sat),
+            Ok(ymd(1403, 12, 25))
+        );
+        assert_eq!(
+            rule.date_from_week(1404, 42, Weekday::Sat),
+            Ok(ymd(1404, 10, 6))
+        );
+        assert_eq!(
+            rule.date_from_week(1404, 0, Weekday::Sat),
+            Err(JalaliError::InvalidWeek {
+                year: 1404,
+                week: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_first_full_week() {
+        let rule = WeekRule::new(Weekday::Sat, FirstWeek::FirstFullWeek);
+        // Farvardin 1 and 2 of 1404 come before the first Saturday.
+        let week = ymd(1404, 1, 1).week_of_year(rule);
+        assert_eq!((week.year(), week.week()), (1403, 52));
+        assert_eq!(ymd(1404, 1, 2).week_of_year(rule).week(), 1);
+
+        let monday = WeekRule::new(Weekday::Mon, FirstWeek::FirstFullWeek);
+        assert_eq!(
+            monday.date_from_week(1404, 1, Weekday::Mon),
+            Ok(ymd(1404, 1, 4))
+        );
+    }
+
+    #[test]
+    fn test_week_round_trip() {
+        for rule in [
+            WeekRule::default(),
+            WeekRule::new(Weekday::Mon, FirstWeek::ContainsNewYear),
+            WeekRule::new(Weekday::Sun, FirstWeek::FirstFullWeek),
+        ] {
+            for year in 1395..1410 {
+                let weeks = rule.weeks_in_year(year);
+                assert!(weeks == 52 || weeks == 53, "{} has {} weeks", year, weeks);
+            }
+            let mut date = ymd(1399, 1, 1);
+            while date < ymd(1406, 1, 1) {
+                let week = date.week_of_year(rule);
+                assert_eq!(
+                    rule.date_from_week(week.year(), week.week(), date.weekday()),
+                    Ok(date)
+                );
+                date = date.checked_add_days(1).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_weeks_at_range_ends() {
+        for rule in [
+            WeekRule::default(),
+            WeekRule::new(Weekday::Sun, FirstWeek::FirstFullWeek),
+        ] {
+            for date in [ymd(MIN_JALALI_YEAR, 1, 1), ymd(MAX_JALALI_YEAR, 12, 29)] {
+                let week = date.week_of_year(rule);
+                assert_eq!(week.year(), date.year());
+                assert_eq!(
+                    rule.date_from_week(week.year(), week.week(), date.weekday()),
+                    Ok(date)
+                );
+            }
+        }
+        assert_eq!(
+            WeekRule::default().date_from_week(MAX_JALALI_YEAR + 1, 1, Weekday::Sat),
+            Err(JalaliError::OutOfRange)
+        );
+    }
+}
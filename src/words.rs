@@ -0,0 +1,280 @@
+//! Numbers spelled out in Persian and English words.
+//!
+//! Persian uses the long scale of the Academy of Persian Language (میلیارد
+//! for 10^9, بیلیون for 10^12); English uses the short scale (billion for
+//! 10^9, trillion for 10^12). Both cover the whole `i64` range.
+
+const PERSIAN_ONES: [&str; 20] = [
+    "صفر",
+    "یک",
+    "دو",
+    "سه",
+    "چهار",
+    "پنج",
+    "شش",
+    "هفت",
+    "هشت",
+    "نه",
+    "ده",
+    "یازده",
+    "دوازده",
+    "سیزده",
+    "چهارده",
+    "پانزده",
+    "شانزده",
+    "هفده",
+    "هجده",
+    "نوزده",
+];
+
+const PERSIAN_TENS: [&str; 10] = [
+    "",
+    "",
+    "بیست",
+    "سی",
+    "چهل",
+    "پنجاه",
+    "شصت",
+    "هفتاد",
+    "هشتاد",
+    "نود",
+];
+
+const PERSIAN_HUNDREDS: [&str; 10] = [
+    "",
+    "صد",
+    "دویست",
+    "سیصد",
+    "چهارصد",
+    "پانصد",
+    "ششصد",
+    "هفتصد",
+    "هشتصد",
+    "نهصد",
+];
+
+const PERSIAN_SCALES: [&str; 7] = [
+    "",
+    "هزار",
+    "میلیون",
+    "میلیارد",
+    "بیلیون",
+    "بیلیارد",
+    "تریلیون",
+];
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ENGLISH_SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+// Groups of three digits, least significant first.
+fn groups(mut n: u64) -> Vec<u64> {
+    let mut groups = Vec::new();
+    while n > 0 {
+        groups.push(n % 1000);
+        n /= 1000;
+    }
+    groups
+}
+
+fn persian_group(n: u64) -> String {
+    let mut parts = Vec::new();
+    if n >= 100 {
+        parts.push(PERSIAN_HUNDREDS[(n / 100) as usize]);
+    }
+    let rest = (n % 100) as usize;
+    if rest >= 20 {
+        parts.push(PERSIAN_TENS[rest / 10]);
+        if !rest.is_multiple_of(10) {
+            parts.push(PERSIAN_ONES[rest % 10]);
+        }
+    } else if rest != 0 {
+        parts.push(PERSIAN_ONES[rest]);
+    }
+    parts.join(" و ")
+}
+
+/// `n` in Persian words, e.g. 1403 is "هزار و چهارصد و سه".
+pub fn persian_cardinal(n: i64) -> String {
+    if n == 0 {
+        return PERSIAN_ONES[0].to_string();
+    }
+    let mut parts = Vec::new();
+    for (scale, &group) in groups(n.unsigned_abs()).iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        // A thousand is "هزار", not "یک هزار"; larger scales keep the "یک".
+        parts.push(match scale {
+            0 => persian_group(group),
+            1 if group == 1 => PERSIAN_SCALES[1].to_string(),
+            _ => format!("{} {}", persian_group(group), PERSIAN_SCALES[scale]),
+        });
+    }
+    let words = parts.join(" و ");
+    if n < 0 {
+        format!("منفی {}", words)
+    } else {
+        words
+    }
+}
+
+/// `n` as a Persian ordinal, e.g. 25 is "بیست و پنجم" and 3 is "سوم".
+pub fn persian_ordinal(n: i64) -> String {
+    let cardinal = persian_cardinal(n);
+    if let Some(stem) = cardinal.strip_suffix("سه") {
+        format!("{}سوم", stem)
+    } else if cardinal.ends_with('ی') {
+        format!("{}\u{200c}ام", cardinal)
+    } else {
+        format!("{}م", cardinal)
+    }
+}
+
+fn english_group(n: u64) -> String {
+    let mut words = String::new();
+    if n >= 100 {
+        words.push_str(ENGLISH_ONES[(n / 100) as usize]);
+        words.push_str(" hundred");
+    }
+    let rest = (n % 100) as usize;
+    if rest != 0 {
+        if !words.is_empty() {
+            words.push_str(" and ");
+        }
+        if rest >= 20 {
+            words.push_str(ENGLISH_TENS[rest / 10]);
+            if !rest.is_multiple_of(10) {
+                words.push('-');
+                words.push_str(ENGLISH_ONES[rest % 10]);
+            }
+        } else {
+            words.push_str(ENGLISH_ONES[rest]);
+        }
+    }
+    words
+}
+
+/// `n` in English words, e.g. 1403 is "one thousand four hundred and three".
+pub fn english_cardinal(n: i64) -> String {
+    if n == 0 {
+        return ENGLISH_ONES[0].to_string();
+    }
+    let groups = groups(n.unsigned_abs());
+    let mut words = String::new();
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        if !words.is_empty() {
+            // "one thousand and three", as "one hundred and three".
+            words.push_str(if scale == 0 && group < 100 {
+                " and "
+            } else {
+                " "
+            });
+        }
+        words.push_str(&english_group(group));
+        if scale > 0 {
+            words.push(' ');
+            words.push_str(ENGLISH_SCALES[scale]);
+        }
+    }
+    if n < 0 {
+        format!("minus {}", words)
+    } else {
+        words
+    }
+}
+
+/// `n` as an English ordinal, e.g. 25 is "twenty-fifth".
+pub fn english_ordinal(n: i64) -> String {
+    let cardinal = english_cardinal(n);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match last.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", last),
+        },
+    };
+    format!("{}{}", head, last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persian_words() {
+        assert_eq!(persian_cardinal(0), "صفر");
+        assert_eq!(persian_cardinal(1403), "هزار و چهارصد و سه");
+        assert_eq!(persian_cardinal(-215), "منفی دویست و پانزده");
+        assert_eq!(persian_cardinal(1_000_000), "یک میلیون");
+        assert_eq!(persian_cardinal(2_000_000_001_000), "دو بیلیون و هزار");
+        assert_eq!(persian_ordinal(25), "بیست و پنجم");
+        assert_eq!(persian_ordinal(3), "سوم");
+        assert_eq!(persian_ordinal(23), "بیست و سوم");
+        assert_eq!(persian_ordinal(13), "سیزدهم");
+        assert_eq!(persian_ordinal(30), "سی\u{200c}ام");
+        assert_eq!(persian_ordinal(1), "یکم");
+        // Every i64 has a spelling.
+        assert!(persian_cardinal(i64::MIN).starts_with("منفی نه تریلیون"));
+    }
+
+    #[test]
+    fn test_english_words() {
+        assert_eq!(
+            english_cardinal(1403),
+            "one thousand four hundred and three"
+        );
+        assert_eq!(english_cardinal(1003), "one thousand and three");
+        assert_eq!(english_cardinal(1_000_000_000_000), "one trillion");
+        assert_eq!(english_cardinal(-40), "minus forty");
+        assert_eq!(english_ordinal(25), "twenty-fifth");
+        assert_eq!(english_ordinal(30), "thirtieth");
+        assert_eq!(english_ordinal(12), "twelfth");
+        assert_eq!(english_ordinal(101), "one hundred and first");
+        assert_eq!(english_ordinal(0), "zeroth");
+    }
+}
@@ -0,0 +1,529 @@
+//! Zoroastrian (Yazdgerdi) calendars.
+//!
+//! Every reckoning has twelve months of 30 days and five Gatha days, kept
+//! here as month 13, and counts years from the accession of Yazdegerd III
+//! in 632. Each day of a month has its own name (Ormazd, Bahman, ...).
+//!
+//! - Fasli starts its year with the Jalali one at Nowruz, as the calendar
+//!   is kept in Iran, and adds a sixth Gatha day (Avardad-sal-Gah) in Jalali
+//!   leap years. Its years are `Era::Yazdgerdi` years.
+//! - Shahanshahi and Qadimi never add a leap day, so they drift through the
+//!   seasons. Qadimi starts on 16 June 632 (Julian); Shahanshahi inserted a
+//!   month once and runs 30 days behind it.
+
+use std::fmt;
+
+use crate::{
+    Era, JalaliDate, JalaliError, Locale, MAX_JALALI_YEAR, MIN_JALALI_YEAR, is_leap,
+    jalali_to_jdn_internal, to_persian_numerals,
+};
+
+pub const GATHA_MONTH: u8 = 13;
+
+const DAYS_IN_MONTH: i64 = 30;
+const DAYS_IN_VAGUE_YEAR: i64 = 365;
+const QADIMI_EPOCH_JDN: i64 = 1_952_063; // 1 Fravardin 1 = 632-06-16 Julian
+const SHAHANSHAHI_EPOCH_JDN: i64 = QADIMI_EPOCH_JDN + DAYS_IN_MONTH;
+
+const MONTH_NAMES: [&str; 13] = [
+    "Fravardin",
+    "Ardibehesht",
+    "Khordad",
+    "Tir",
+    "Amardad",
+    "Shehrevar",
+    "Meher",
+    "Avan",
+    "Adar",
+    "Dae",
+    "Bahman",
+    "Aspandarmad",
+    "Gatha",
+];
+
+const PERSIAN_MONTH_NAMES: [&str; 13] = [
+    "فروردین",
+    "اردیبهشت",
+    "خرداد",
+    "تیر",
+    "امرداد",
+    "شهریور",
+    "مهر",
+    "آبان",
+    "آذر",
+    "دی",
+    "بهمن",
+    "سپندارمذ",
+    "پنجه",
+];
+
+const DAY_NAMES: [&str; 30] = [
+    "Ormazd",
+    "Bahman",
+    "Ardibehesht",
+    "Shehrevar",
+    "Aspandarmad",
+    "Khordad",
+    "Amardad",
+    "Depadar",
+    "Adar",
+    "Avan",
+    "Khorshed",
+    "Mohor",
+    "Tir",
+    "Gosh",
+    "Depmeher",
+    "Meher",
+    "Srosh",
+    "Rashne",
+    "Fravardin",
+    "Behram",
+    "Ram",
+    "Govad",
+    "Depdin",
+    "Din",
+    "Ashishvangh",
+    "Ashtad",
+    "Asman",
+    "Zamyad",
+    "Mareshpand",
+    "Aneran",
+];
+
+const PERSIAN_DAY_NAMES: [&str; 30] = [
+    "هرمزد",
+    "بهمن",
+    "اردیبهشت",
+    "شهریور",
+    "سپندارمذ",
+    "خرداد",
+    "امرداد",
+    "دی‌به‌آذر",
+    "آذر",
+    "آبان",
+    "خور",
+    "ماه",
+    "تیر",
+    "گوش",
+    "دی‌به‌مهر",
+    "مهر",
+    "سروش",
+    "رشن",
+    "فروردین",
+    "بهرام",
+    "رام",
+    "باد",
+    "دی‌به‌دین",
+    "دین",
+    "ارد",
+    "اشتاد",
+    "آسمان",
+    "زامیاد",
+    "مانتره‌سپند",
+    "انارام",
+];
+
+const GATHA_NAMES: [&str; 6] = [
+    "Ahunavad",
+    "Ushtavad",
+    "Spentomad",
+    "Vohukhshathra",
+    "Vahishtoisht",
+    "Avardad-sal-Gah",
+];
+
+const PERSIAN_GATHA_NAMES: [&str; 6] = [
+    "اهنود",
+    "اشتود",
+    "سپنتمد",
+    "وهوخشتر",
+    "وهیشتوایشت",
+    "اورداد",
+];
+
+/// Which Zoroastrian reckoning a date is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ZoroastrianCalendar {
+    #[default]
+    Fasli,
+    Shahanshahi,
+    Qadimi,
+}
+
+impl ZoroastrianCalendar {
+    /// Julian Day Number of 1 Fravardin of `year`.
+    fn new_year_jdn(self, year: i32) -> i64 {
+        match self {
+            ZoroastrianCalendar::Fasli => {
+                jalali_to_jdn_internal(Era::Yazdgerdi.year_to_solar_hijri(year), 1, 1)
+            }
+            ZoroastrianCalendar::Shahanshahi => {
+                SHAHANSHAHI_EPOCH_JDN + (year as i64 - 1) * DAYS_IN_VAGUE_YEAR
+            }
+            ZoroastrianCalendar::Qadimi => {
+                QADIMI_EPOCH_JDN + (year as i64 - 1) * DAYS_IN_VAGUE_YEAR
+            }
+        }
+    }
+
+    /// 6 Gatha days in Fasli years that fall in Jalali leap years, else 5.
+    pub fn gatha_days(self, year: i32) -> u8 {
+        match self {
+            ZoroastrianCalendar::Fasli if is_leap(Era::Yazdgerdi.year_to_solar_hijri(year)) => 6,
+            _ => 5,
+        }
+    }
+
+    fn year_range(self) -> std::ops::RangeInclusive<i32> {
+        match self {
+            ZoroastrianCalendar::Fasli => {
+                Era::Yazdgerdi.year_from_solar_hijri(MIN_JALALI_YEAR)
+                    ..=Era::Yazdgerdi.year_from_solar_hijri(MAX_JALALI_YEAR)
+            }
+            _ => 1..=MAX_JALALI_YEAR,
+        }
+    }
+}
+
+impl fmt::Display for ZoroastrianCalendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ZoroastrianCalendar::Fasli => "Fasli",
+            ZoroastrianCalendar::Shahanshahi => "Shahanshahi",
+            ZoroastrianCalendar::Qadimi => "Qadimi",
+        })
+    }
+}
+
+/// A date in one of the Zoroastrian calendars. The Gatha days are month
+/// `GATHA_MONTH`, days 1-5 (or 6).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZoroastrianDate {
+    calendar: ZoroastrianCalendar,
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl ZoroastrianDate {
+    pub fn try_new(
+        calendar: ZoroastrianCalendar,
+        year: i32,
+        month: u8,
+        day: u8,
+    ) -> Result<ZoroastrianDate, JalaliError> {
+        if !(1..=GATHA_MONTH).contains(&month) {
+            return Err(JalaliError::InvalidMonth {
+                month: month as u32,
+            });
+        }
+        if !calendar.year_range().contains(&year) {
+            return Err(JalaliError::OutOfRange);
+        }
+        let days = if month == GATHA_MONTH {
+            calendar.gatha_days(year)
+        } else {
+            DAYS_IN_MONTH as u8
+        };
+        if !(1..=days).contains(&day) {
+            return Err(JalaliError::InvalidDay {
+                year,
+                month: month as u32,
+                day: day as u32,
+            });
+        }
+        Ok(ZoroastrianDate {
+            calendar,
+            year,
+            month,
+            day,
+        })
+    }
+
+    pub fn try_from_jdn(
+        calendar: ZoroastrianCalendar,
+        jdn: i64,
+    ) -> Result<ZoroastrianDate, JalaliError> {
+        let (year, ordinal) = match calendar {
+            ZoroastrianCalendar::Fasli => {
+                let date = JalaliDate::try_from_jdn(jdn)?;
+                (date.era_year(Era::Yazdgerdi), date.ordinal() as i64)
+            }
+            _ => {
+                let days = jdn
+                    .checked_sub(calendar.new_year_jdn(1))
+                    .ok_or(JalaliError::OutOfRange)?;
+                let year = i32::try_from(days.div_euclid(DAYS_IN_VAGUE_YEAR) + 1)
+                    .map_err(|_| JalaliError::OutOfRange)?;
+                (year, days.rem_euclid(DAYS_IN_VAGUE_YEAR) + 1)
+            }
+        };
+        let month = ((ordinal - 1) / DAYS_IN_MONTH + 1).min(GATHA_MONTH as i64);
+        let day = ordinal - (month - 1) * DAYS_IN_MONTH;
+        ZoroastrianDate::try_new(calendar, year, month as u8, day as u8)
+    }
+
+    pub fn from_jalali(
+        calendar: ZoroastrianCalendar,
+        date: JalaliDate,
+    ) -> Result<ZoroastrianDate, JalaliError> {
+        ZoroastrianDate::try_from_jdn(calendar, date.to_jdn())
+    }
+
+    pub fn to_jdn(self) -> i64 {
+        self.calendar.new_year_jdn(self.year)
+            + (self.month as i64 - 1) * DAYS_IN_MONTH
+            + self.day as i64
+            - 1
+    }
+
+    pub fn to_jalali(self) -> Result<JalaliDate, JalaliError> {
+        JalaliDate::try_from_jdn(self.to_jdn())
+    }
+
+    pub fn calendar(&self) -> ZoroastrianCalendar {
+        self.calendar
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn is_gatha(&self) -> bool {
+        self.month == GATHA_MONTH
+    }
+
+    /// Month name, or "Gatha" for the Gatha days; in Persian for locales
+    /// written in Arabic script.
+    pub fn month_name(&self, locale: Locale) -> &'static str {
+        let names = if locale.is_arabic_script() {
+            &PERSIAN_MONTH_NAMES
+        } else {
+            &MONTH_NAMES
+        };
+        names[self.month as usize - 1]
+    }
+
+    /// Name of the day (Ormazd, Bahman, ...), or of the Gatha day; in
+    /// Persian for locales written in Arabic script.
+    pub fn day_name(&self, locale: Locale) -> &'static str {
+        let index = self.day as usize - 1;
+        match (self.is_gatha(), locale.is_arabic_script()) {
+            (false, false) => DAY_NAMES[index],
+            (false, true) => PERSIAN_DAY_NAMES[index],
+            (true, false) => GATHA_NAMES[index],
+            (true, true) => PERSIAN_GATHA_NAMES[index],
+        }
+    }
+
+    /// Formats with `%Y`, `%m`, `%d`, `%e`, `%B` (month name), `%A` (day
+    /// name) and `%K` (calendar name; `%C` is the century in
+    /// `JalaliDate::format`), plus `%n`, `%t` and `%%`; `%-d` drops the
+    /// padding. Nothing is rendered until it is displayed.
+    pub fn format<'a>(&self, pattern: &'a str) -> ZoroastrianFormat<'a> {
+        ZoroastrianFormat {
+            date: *self,
+            pattern,
+            locale: Locale::Latin,
+        }
+    }
+}
+
+impl fmt::Display for ZoroastrianDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}/{:02}/{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A Zoroastrian date paired with a format pattern.
+#[derive(Debug, Clone)]
+pub struct ZoroastrianFormat<'a> {
+    date: ZoroastrianDate,
+    pattern: &'a str,
+    locale: Locale,
+}
+
+impl ZoroastrianFormat<'_> {
+    /// Writes names in `locale`, with Persian digits if it uses them.
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+impl fmt::Display for ZoroastrianFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = &self.date;
+        let mut out = String::new();
+        let mut chars = self.pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let unpadded = chars.next_if_eq(&'-').is_some();
+            let number = |value: i64, width: usize, pad: char| {
+                let text = value.to_string();
+                let padded = if unpadded || text.len() >= width {
+                    text
+                } else {
+                    pad.to_string().repeat(width - text.len()) + &text
+                };
+                if self.locale.native_digits() {
+                    to_persian_numerals(&padded)
+                } else {
+                    padded
+                }
+            };
+            match chars.next() {
+                Some('Y') => out.push_str(&number(date.year as i64, 4, '0')),
+                Some('m') => out.push_str(&number(date.month as i64, 2, '0')),
+                Some('d') => out.push_str(&number(date.day as i64, 2, '0')),
+                Some('e') => out.push_str(&number(date.day as i64, 2, ' ')),
+                Some('B') => out.push_str(date.month_name(self.locale)),
+                Some('A') => out.push_str(date.day_name(self.locale)),
+                Some('K') => out.push_str(&date.calendar.to_string()),
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    out.push('%');
+                    if unpadded {
+                        out.push('-');
+                    }
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        f.write_str(&out)
+    }
+}
+
+pub fn try_jalali_to_zoroastrian(
+    calendar: ZoroastrianCalendar,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let date = ZoroastrianDate::from_jalali(calendar, JalaliDate::try_from_ymd(jy, jm, jd)?)?;
+    Ok((date.year, date.month, date.day))
+}
+
+pub fn jalali_to_zoroastrian(
+    calendar: ZoroastrianCalendar,
+    jy: i32,
+    jm: u8,
+    jd: u8,
+) -> (i32, u8, u8) {
+    try_jalali_to_zoroastrian(calendar, jy, jm, jd).unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+pub fn try_zoroastrian_to_jalali(
+    calendar: ZoroastrianCalendar,
+    year: i32,
+    month: u8,
+    day: u8,
+) -> Result<(i32, u8, u8), JalaliError> {
+    let date = ZoroastrianDate::try_new(calendar, year, month, day)?.to_jalali()?;
+    Ok((date.year(), date.month(), date.day()))
+}
+
+pub fn zoroastrian_to_jalali(
+    calendar: ZoroastrianCalendar,
+    year: i32,
+    month: u8,
+    day: u8,
+) -> (i32, u8, u8) {
+    try_zoroastrian_to_jalali(calendar, year, month, day)
+        .unwrap_or_else(|e| panic!("Error: {}.", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gregorian_to_jdn;
+
+    #[test]
+    fn test_fasli() {
+        let fasli = ZoroastrianCalendar::Fasli;
+        assert_eq!(jalali_to_zoroastrian(fasli, 1403, 1, 1), (1393, 1, 1));
+        assert_eq!(jalali_to_zoroastrian(fasli, 1403, 5, 12), (1393, 5, 16));
+        assert_eq!(jalali_to_zoroastrian(fasli, 1403, 12, 26), (1393, 13, 2));
+        // 1403 is a Jalali leap year, so Fasli 1393 has a sixth Gatha day.
+        assert_eq!(jalali_to_zoroastrian(fasli, 1403, 12, 30), (1393, 13, 6));
+        assert_eq!(zoroastrian_to_jalali(fasli, 1393, 13, 6), (1403, 12, 30));
+        assert_eq!(
+            try_zoroastrian_to_jalali(fasli, 1394, 13, 6),
+            Err(JalaliError::InvalidDay {
+                year: 1394,
+                month: 13,
+                day: 6
+            })
+        );
+
+        let date = ZoroastrianDate::try_new(fasli, 1393, 5, 16).unwrap();
+        assert_eq!(
+            date.format("%A, %-d %B %Y").to_string(),
+            "Meher, 16 Amardad 1393"
+        );
+        assert_eq!(
+            date.format("روز %A %B %Y")
+                .locale(Locale::Persian)
+                .to_string(),
+            "روز مهر امرداد ۱۳۹۳"
+        );
+        let gatha = ZoroastrianDate::try_new(fasli, 1393, 13, 6).unwrap();
+        assert_eq!(
+            gatha.format("%A (%B %d)").to_string(),
+            "Avardad-sal-Gah (Gatha 06)"
+        );
+        assert_eq!(
+            date.format("%Y %K %C").to_string(),
+            format!("1393 {} %C", fasli)
+        );
+    }
+
+    #[test]
+    fn test_shahanshahi_and_qadimi() {
+        // Parsi New Year 1394 was 15 August 2024; the Qadimi one a month earlier.
+        let navroz =
+            ZoroastrianDate::try_new(ZoroastrianCalendar::Shahanshahi, 1394, 1, 1).unwrap();
+        assert_eq!(navroz.to_jdn(), gregorian_to_jdn(2024, 8, 15));
+        assert_eq!(navroz.day_name(Locale::Latin), "Ormazd");
+        assert_eq!(
+            zoroastrian_to_jalali(ZoroastrianCalendar::Qadimi, 1394, 1, 1),
+            (1403, 4, 26)
+        );
+        assert_eq!(
+            jalali_to_zoroastrian(ZoroastrianCalendar::Shahanshahi, 1403, 5, 24),
+            (1393, 13, 5)
+        );
+
+        for calendar in [
+            ZoroastrianCalendar::Fasli,
+            ZoroastrianCalendar::Shahanshahi,
+            ZoroastrianCalendar::Qadimi,
+        ] {
+            let start = gregorian_to_jdn(2023, 1, 1);
+            for jdn in start..start + 2 * 366 {
+                let date = ZoroastrianDate::try_from_jdn(calendar, jdn).unwrap();
+                assert_eq!(date.to_jdn(), jdn, "{} {}", calendar, date);
+            }
+        }
+        for jdn in [i64::MIN, i64::MAX] {
+            assert_eq!(
+                ZoroastrianDate::try_from_jdn(ZoroastrianCalendar::Qadimi, jdn),
+                Err(JalaliError::OutOfRange)
+            );
+        }
+    }
+}
@@ -4,6 +4,9 @@ use std::str;
 fn run_command(program_name: &str, args: &[&str]) -> (String, String, i32) {
     let mut cmd = Command::new(format!("target/debug/{}", program_name));
     cmd.args(args);
+    // Names follow LC_TIME/LANG; keep the expected output independent of the
+    // caller's locale.
+    cmd.env("LC_ALL", "C");
 
     let output = cmd.output().expect("Failed to execute command");
 
@@ -121,6 +124,28 @@ fn test_jdate_jalali_to_gregorian() {
     );
 }
 
+#[test]
+fn test_jdate_jalali_to_gregorian_other_formats() {
+    for input in ["1402.07.15", "15 Mehr 1402", "۱۵ مهر ۱۴۰۲"] {
+        let (stdout, stderr, exit_code) = run_command("jdate", &["-g", input]);
+        assert_eq!(
+            exit_code, 0,
+            "jdate -g {} failed. Stderr: {}",
+            input, stderr
+        );
+        assert_eq!(
+            stdout.trim(),
+            "Sat Oct 07 00:00:00 UTC 2023",
+            "Input: {}",
+            input
+        );
+    }
+
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-g", "1402/07/15 14:30"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "Sat Oct 07 14:30:00 UTC 2023");
+}
+
 #[test]
 fn test_jdate_gregorian_to_jalali() {
     // Test `jdate -j 2020/3/20` for Gregorian to Jalali
@@ -140,6 +165,258 @@ fn test_jdate_gregorian_to_jalali() {
     );
 }
 
+#[test]
+fn test_jdate_early_year_is_not_padded() {
+    // The default and RFC 2822 outputs print years below 1000 as they are.
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-j", "622/3/22"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Jom Farvardin 01 00:00:00 UTC 1");
+
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-u", "-R", "-E", "5"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(stdout.contains(" Esfand 4 "), "Stdout: {}", stdout);
+}
+
+#[test]
+fn test_jdate_equinox_in_timezone() {
+    // Tahvil-e Sal 1404 was at 12:31 Tehran time on 30 Esfand 1403.
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-E", "1404", "-z", "Asia/Tehran"]);
+
+    assert_eq!(
+        exit_code, 0,
+        "jdate -E 1404 should exit with 0. Stderr: {}",
+        stderr
+    );
+    assert!(
+        stdout.contains("Esfand 30 12:3") && stdout.contains("1403"),
+        "Expected Tahvil-e Sal around 1403/12/30 12:31. Got: {}. Stderr: {}",
+        stdout,
+        stderr
+    );
+
+    let (_, stderr, exit_code) = run_command("jdate", &["-E", "1404", "-g", "1404/01/01"]);
+    assert_ne!(
+        exit_code, 0,
+        "-E together with -g should be rejected. Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_jdate_adjust_months_on_jalali_fields() {
+    // Starting from 30 Esfand 1403, one month later is 30 Farvardin 1404 and
+    // one year later clamps to 29 Esfand 1404.
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["-E", "1404", "-z", "Asia/Tehran", "-v", "+1m"]);
+    assert_eq!(
+        exit_code, 0,
+        "jdate -v +1m should exit with 0. Stderr: {}",
+        stderr
+    );
+    assert!(
+        stdout.contains("Farvardin 30") && stdout.contains("1404"),
+        "Expected 1404/01/30. Got: {}. Stderr: {}",
+        stdout,
+        stderr
+    );
+
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["-E", "1404", "-z", "Asia/Tehran", "-v", "+1y"]);
+    assert_eq!(
+        exit_code, 0,
+        "jdate -v +1y should exit with 0. Stderr: {}",
+        stderr
+    );
+    assert!(
+        stdout.contains("Esfand 29") && stdout.contains("1404"),
+        "Expected 1404/12/29. Got: {}. Stderr: {}",
+        stdout,
+        stderr
+    );
+}
+
+#[test]
+fn test_jdate_adjust_hours_across_dst() {
+    // Clocks in New York went from 02:00 to 03:00 on 20 Esfand 1402.
+    let (stdout, stderr, exit_code) = run_command(
+        "jdate",
+        &[
+            "-z",
+            "America/New_York",
+            // Day 1 first, so setting Esfand works on any day of the month.
+            "-v",
+            "1d",
+            "-v",
+            "1402y",
+            "-v",
+            "12m",
+            "-v",
+            "20d",
+            "-v",
+            "1H",
+            "-v",
+            "30M",
+            "-v",
+            "0S",
+            "-v",
+            "+1H",
+            "-I=seconds",
+        ],
+    );
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "1402-12-20T03:30:00");
+}
+
+#[test]
+fn test_jdate_set_out_of_range_values() {
+    // Values too large for their field are errors, not wrapped around.
+    for (adjustment, message) in [
+        ("4294968699y", "Invalid Jalali year: 4294968699"),
+        ("257m", "Invalid Jalali month: 257"),
+        ("257d", "Invalid Jalali day: 257"),
+        ("4294967310H", "Invalid hour: 4294967310"),
+        ("4294967326M", "Invalid minute: 4294967326"),
+        ("4294967326S", "Invalid second: 4294967326"),
+    ] {
+        let (_, stderr, exit_code) = run_command("jdate", &["-v", adjustment]);
+        assert_ne!(exit_code, 0, "-v {} should fail", adjustment);
+        assert!(stderr.contains(message), "Stderr: {}", stderr);
+    }
+}
+
+#[test]
+fn test_jdate_set_week_of_year() {
+    // Week 1 of 1404 began on Saturday 25 Esfand 1403.
+    let (stdout, stderr, exit_code) = run_command(
+        "jdate",
+        &["-E", "1404", "-z", "Asia/Tehran", "-v", "42w", "-I"],
+    );
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout.trim(), "1404-10-06");
+}
+
+#[test]
+fn test_jdate_diff() {
+    let (stdout, stderr, exit_code) = run_command("jdate", &["--diff", "1399/12/30", "1403/05/10"]);
+    assert_eq!(
+        exit_code, 0,
+        "jdate --diff should exit with 0. Stderr: {}",
+        stderr
+    );
+    assert_eq!(stdout, "1229 days (3 years, 4 months, 11 days)");
+
+    let (_, stderr, exit_code) = run_command("jdate", &["--diff", "1400/12/30", "1403/05/10"]);
+    assert_ne!(
+        exit_code, 0,
+        "jdate --diff with an invalid date should fail. Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_jdate_locale() {
+    // 3 August 2024 is Shanbeh 13 Mordad 1403.
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["--locale", "prs-latn", "-j", "2024/08/03"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Sha Asad 13 00:00:00 UTC 1403");
+
+    let (stdout, stderr, exit_code) = run_command("jdate", &["--locale", "ps", "-j", "2024/08/03"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "شنبه زمری ۱۳ ۰۰:۰۰:۰۰ UTC ۱۴۰۳");
+
+    let (_, stderr, exit_code) = run_command("jdate", &["--locale", "xx", "-j", "2024/08/03"]);
+    assert_ne!(exit_code, 0, "Unknown locales should be rejected");
+    assert!(stderr.contains("Unknown locale 'xx'"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_jdate_era() {
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["--era", "imperial", "-j", "2024/08/02"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Jom Mordad 12 00:00:00 UTC 2583");
+
+    // Input years are read in the chosen era, or in the one they name.
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["--era", "yazdgerdi", "-g", "1393/05/12"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Fri Aug 02 00:00:00 UTC 2024");
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-g", "12 Mordad 2583 Imperial"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Fri Aug 02 00:00:00 UTC 2024");
+
+    // jcal reads its year in the era too; -P still takes a Solar Hijri year.
+    let (stdout, stderr, exit_code) = run_command("jcal", &["--era", "imperial", "2583"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(stdout.starts_with("2583 (Imperial)"), "Stdout: {}", stdout);
+    let (stdout, stderr, exit_code) = run_command("jcal", &["-P", "1357"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(stdout.starts_with("2537 (Pahlavi)"), "Stdout: {}", stdout);
+
+    // Years set with -v and the year of -E are read in the era as well.
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["--era", "imperial", "-u", "-E", "2583"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Cha Farvardin 01 03:06:30 UTC 2583");
+}
+
+#[test]
+fn test_persian_digit_arguments() {
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-g", "۱۴۰۳/۰۵/۱۰"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Wed Jul 31 00:00:00 UTC 2024");
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-g", "\u{feff}\u{200b}1403/05/10"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Wed Jul 31 00:00:00 UTC 2024");
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-j", "٢٠٢٤/٠٧/٣١"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "Cha Mordad 10 00:00:00 UTC 1403");
+    let (stdout, stderr, exit_code) = run_command("jdate", &["--diff", "۱۴۰۳/۰۱/۰۱", "1403/02/01"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(stdout.starts_with("31 days"), "Stdout: {}", stdout);
+
+    let (stdout, stderr, exit_code) = run_command("jcal", &["۱۴۰۳"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(
+        stdout.trim_start().starts_with("1403"),
+        "Stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_jdate_written() {
+    let (stdout, stderr, exit_code) =
+        run_command("jdate", &["-W", "--locale", "fa", "-j", "2024/12/15"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(stdout, "بیست و پنجم آذر هزار و چهارصد و سه");
+    let (stdout, stderr, exit_code) = run_command("jdate", &["-W", "-j", "2024/12/15"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert_eq!(
+        stdout,
+        "twenty-fifth of Azar, one thousand four hundred and three"
+    );
+
+    let (_, stderr, exit_code) = run_command("jdate", &["-W", "-R"]);
+    assert_eq!(exit_code, 1);
+    assert!(stderr.contains("cannot be combined"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_jcal_locale() {
+    let (stdout, stderr, exit_code) = run_command("jcal", &["--locale", "prs", "1403"]);
+    assert_eq!(exit_code, 0, "Stderr: {}", stderr);
+    assert!(
+        stdout.contains("حمل") && stdout.contains("حوت") && stdout.contains("۱۴۰۳"),
+        "Year view should use Dari month names and Persian digits. Stdout: {}",
+        stdout
+    );
+
+    let (_, _, exit_code) = run_command("jcal", &["-p", "--locale", "fa", "1403"]);
+    assert_ne!(exit_code, 0, "-p and --locale should not be combined");
+}
+
 #[test]
 fn test_g2j_unix_epoch() {
     // Unix epoch: 1970/1/1